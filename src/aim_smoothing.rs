@@ -0,0 +1,65 @@
+//! 让瞄准移动更接近人手操作：对移动目标做速度预判（lead），
+//! 并把单帧修正量限制、缓出，避免光标瞬移到目标点。
+
+use std::time::Instant;
+
+/// 目标跳变阈值（像素）：最近目标框的中心点若单帧内移动超过该距离，
+/// 视为切换到了不同目标，重置速度估计，避免产生虚假的预判速度。
+const SWITCH_THRESHOLD: f32 = 80.0;
+
+/// 跟踪被瞄准目标（最近的检测框）中心点，按 `v = Δcenter / Δt` 估算其
+/// 屏幕空间速度，供上层做移动预判。
+pub struct TargetTracker {
+    last_center: Option<(f32, f32)>,
+    last_time: Option<Instant>,
+}
+
+impl TargetTracker {
+    pub fn new() -> Self {
+        Self {
+            last_center: None,
+            last_time: None,
+        }
+    }
+
+    /// 喂入本帧目标中心点，返回估算的速度 (px/s)。
+    pub fn update(&mut self, center: (f32, f32)) -> (f32, f32) {
+        let now = Instant::now();
+        let velocity = match (self.last_center, self.last_time) {
+            (Some(prev), Some(prev_time)) => {
+                let jump = ((center.0 - prev.0).powi(2) + (center.1 - prev.1).powi(2)).sqrt();
+                if jump > SWITCH_THRESHOLD {
+                    (0.0, 0.0)
+                } else {
+                    let dt = now.duration_since(prev_time).as_secs_f32().max(0.001);
+                    ((center.0 - prev.0) / dt, (center.1 - prev.1) / dt)
+                }
+            }
+            _ => (0.0, 0.0),
+        };
+        self.last_center = Some(center);
+        self.last_time = Some(now);
+        velocity
+    }
+
+    /// 目标身份发生变化（例如最近目标框切换）时调用，丢弃旧速度估计。
+    pub fn reset(&mut self) {
+        self.last_center = None;
+        self.last_time = None;
+    }
+}
+
+impl Default for TargetTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 对剩余瞄准偏移量做缓出平滑：每帧只修正 `alpha` 比例的偏移，并把单帧
+/// 像素位移限制在 `max_step` 以内。`alpha = 1.0` 时偏移一次性修正，但仍受
+/// `max_step` 限制，只有偏移不超过 `max_step` 时才等价于旧版的瞬间到位。
+pub fn ease_step(dx: f32, dy: f32, alpha: f32, max_step: f32) -> (f32, f32) {
+    let step_x = (dx * alpha).clamp(-max_step, max_step);
+    let step_y = (dy * alpha).clamp(-max_step, max_step);
+    (step_x, step_y)
+}
@@ -2,8 +2,11 @@ use anyhow::{Context, Result};
 use windows_capture::dxgi_duplication_api::{DxgiDuplicationApi, DxgiDuplicationFormat, Error as DxgiError};
 use windows_capture::monitor::Monitor;
 
+use crate::capture::{CaptureBackend, MonitorInfo};
+
 pub struct CaptureContext {
-    pub dup: DxgiDuplicationApi,
+    dup: DxgiDuplicationApi,
+    monitor_index: usize,
     pub width: u32,
     pub height: u32,
     pub timeout_ms: u32,
@@ -12,80 +15,133 @@ pub struct CaptureContext {
     pub nopad_buffer: Vec<u8>,
 }
 
-fn create_dup() -> Result<DxgiDuplicationApi> {
+/// 枚举所有已连接显示器，索引与 `CaptureContext::create` 的
+/// `monitor_index` 参数一一对应。
+pub fn enumerate_monitors() -> Result<Vec<MonitorInfo>> {
+    let monitors = Monitor::all().context("枚举显示器失败")?;
+    Ok(monitors
+        .into_iter()
+        .enumerate()
+        .map(|(index, monitor)| {
+            let (x, y) = monitor.position().unwrap_or((0, 0));
+            MonitorInfo {
+                index,
+                name: monitor.device_name().unwrap_or_else(|_| format!("显示器 {}", index + 1)),
+                width: monitor.width().unwrap_or(0),
+                height: monitor.height().unwrap_or(0),
+                x,
+                y,
+            }
+        })
+        .collect())
+}
+
+fn select_monitor(monitor_index: usize) -> Result<Monitor> {
+    let monitors = Monitor::all().context("枚举显示器失败")?;
+    monitors
+        .into_iter()
+        .nth(monitor_index)
+        .or_else(Monitor::primary)
+        .context("获取显示器失败")
+}
+
+/// 主显示器的像素宽高，供不依赖 `CaptureContext` 实例的调用方
+/// （例如覆盖层窗口的兜底定位）在检测开始前查询屏幕尺寸。
+pub fn primary_monitor_size() -> Result<(u32, u32)> {
     let monitor = Monitor::primary().context("获取主显示器失败")?;
+    Ok((monitor.width()?, monitor.height()?))
+}
+
+fn create_dup(monitor_index: usize) -> Result<DxgiDuplicationApi> {
+    let monitor = select_monitor(monitor_index)?;
     DxgiDuplicationApi::new_options(monitor, &[DxgiDuplicationFormat::Bgra8])
         .context("创建 DXGI 复制会话失败")
 }
 
-pub fn create_capture_context(timeout_ms: u32) -> Result<CaptureContext> {
-    let dup = create_dup()?;
-    let width = dup.width();
-    let height = dup.height();
-    Ok(CaptureContext {
-        dup,
-        width,
-        height,
-        timeout_ms,
-        rgba_buffer: Vec::new(),
-        rgb_buffer: Vec::new(),
-        nopad_buffer: Vec::new(),
-    })
+impl CaptureContext {
+    /// DXGI 复制会话丢失访问权限（切换全屏应用、UAC 提示等）后重新创建，
+    /// 重新绑定到同一块 `monitor_index` 对应的显示器，而不是退回主显示器。
+    fn recreate(&mut self) -> Result<()> {
+        self.dup = create_dup(self.monitor_index)?;
+        self.width = self.dup.width();
+        self.height = self.dup.height();
+        Ok(())
+    }
 }
 
-pub fn capture_full(ctx: &mut CaptureContext) -> Result<bool> {
-    match ctx.dup.acquire_next_frame(ctx.timeout_ms) {
-        Ok(mut frame) => {
-            let buffer = frame.buffer().context("获取帧缓冲失败")?;
-            let bytes = buffer.as_nopadding_buffer(&mut ctx.nopad_buffer);
-            ctx.rgba_buffer.resize(bytes.len(), 0);
-            ctx.rgba_buffer.copy_from_slice(bytes);
-            Ok(!ctx.rgba_buffer.is_empty())
-        }
-        Err(DxgiError::Timeout) => Ok(false),
-        Err(DxgiError::AccessLost) => {
-            ctx.dup = create_dup()?;
-            ctx.width = ctx.dup.width();
-            ctx.height = ctx.dup.height();
-            Ok(false)
-        }
-        Err(e) => Err(e.into()),
+impl CaptureBackend for CaptureContext {
+    fn create(monitor_index: usize, timeout_ms: u32) -> Result<Self> {
+        let dup = create_dup(monitor_index)?;
+        let width = dup.width();
+        let height = dup.height();
+        Ok(Self {
+            dup,
+            monitor_index,
+            width,
+            height,
+            timeout_ms,
+            rgba_buffer: Vec::new(),
+            rgb_buffer: Vec::new(),
+            nopad_buffer: Vec::new(),
+        })
     }
-}
 
-pub fn capture_region(
-    ctx: &mut CaptureContext,
-    x: u32,
-    y: u32,
-    width: u32,
-    height: u32,
-) -> Result<bool> {
-    if width == 0 || height == 0 {
-        return Ok(false);
+    fn width(&self) -> u32 {
+        self.width
     }
-    let end_x = (x + width).min(ctx.width);
-    let end_y = (y + height).min(ctx.height);
-    if end_x <= x || end_y <= y {
-        return Ok(false);
+
+    fn height(&self) -> u32 {
+        self.height
     }
-    match ctx.dup.acquire_next_frame(ctx.timeout_ms) {
-        Ok(mut frame) => {
-            let buffer = frame
-                .buffer_crop(x, y, end_x, end_y)
-                .context("获取裁剪帧失败")?;
-            let bytes = buffer.as_nopadding_buffer(&mut ctx.nopad_buffer);
-            ctx.rgba_buffer.resize(bytes.len(), 0);
-            ctx.rgba_buffer.copy_from_slice(bytes);
-            Ok(!ctx.rgba_buffer.is_empty())
+
+    fn capture_full(&mut self) -> Result<bool> {
+        match self.dup.acquire_next_frame(self.timeout_ms) {
+            Ok(mut frame) => {
+                let buffer = frame.buffer().context("获取帧缓冲失败")?;
+                let bytes = buffer.as_nopadding_buffer(&mut self.nopad_buffer);
+                self.rgba_buffer.resize(bytes.len(), 0);
+                self.rgba_buffer.copy_from_slice(bytes);
+                Ok(!self.rgba_buffer.is_empty())
+            }
+            Err(DxgiError::Timeout) => Ok(false),
+            Err(DxgiError::AccessLost) => {
+                self.recreate()?;
+                Ok(false)
+            }
+            Err(e) => Err(e.into()),
         }
-        Err(DxgiError::Timeout) => Ok(false),
-        Err(DxgiError::AccessLost) => {
-            ctx.dup = create_dup()?;
-            ctx.width = ctx.dup.width();
-            ctx.height = ctx.dup.height();
-            Ok(false)
+    }
+
+    fn capture_region(&mut self, x: u32, y: u32, width: u32, height: u32) -> Result<bool> {
+        if width == 0 || height == 0 {
+            return Ok(false);
+        }
+        let end_x = (x + width).min(self.width);
+        let end_y = (y + height).min(self.height);
+        if end_x <= x || end_y <= y {
+            return Ok(false);
+        }
+        match self.dup.acquire_next_frame(self.timeout_ms) {
+            Ok(mut frame) => {
+                let buffer = frame
+                    .buffer_crop(x, y, end_x, end_y)
+                    .context("获取裁剪帧失败")?;
+                let bytes = buffer.as_nopadding_buffer(&mut self.nopad_buffer);
+                self.rgba_buffer.resize(bytes.len(), 0);
+                self.rgba_buffer.copy_from_slice(bytes);
+                Ok(!self.rgba_buffer.is_empty())
+            }
+            Err(DxgiError::Timeout) => Ok(false),
+            Err(DxgiError::AccessLost) => {
+                self.recreate()?;
+                Ok(false)
+            }
+            Err(DxgiError::InvalidSize) => Ok(false),
+            Err(e) => Err(e.into()),
         }
-        Err(DxgiError::InvalidSize) => Ok(false),
-        Err(e) => Err(e.into()),
     }
+
+    fn rgba_rgb_buffers(&mut self) -> (&[u8], &mut Vec<u8>) {
+        (&self.rgba_buffer, &mut self.rgb_buffer)
     }
+}
@@ -1,11 +1,77 @@
+#[cfg(windows)]
 pub mod inner;
+#[cfg(not(windows))]
+pub mod x11;
 
-/// Direct3D 11 屏幕捕获上下文
+/// 跨平台屏幕捕获后端：Windows 上是 DXGI 桌面复制，其余平台是 X11
+/// `XGetImage` 截取根窗口；`main.rs` 的检测线程通过这组自由函数、trait
+/// 的 `width`/`height` 与两个后端共有的 `rgba_buffer` 字段访问捕获上下文，
+/// 捕获→RGBA→YOLO 的流水线在两个平台上保持一致。
+pub trait CaptureBackend {
+    /// `monitor_index` 对应 `enumerate_monitors()` 返回的 `MonitorInfo::index`；
+    /// 找不到对应显示器时退回主显示器，语义与旧版 `select_monitor` 一致。
+    fn create(monitor_index: usize, timeout_ms: u32) -> anyhow::Result<Self>
+    where
+        Self: Sized;
+    fn width(&self) -> u32;
+    fn height(&self) -> u32;
+    fn capture_full(&mut self) -> anyhow::Result<bool>;
+    fn capture_region(&mut self, x: u32, y: u32, width: u32, height: u32) -> anyhow::Result<bool>;
+    /// 最近一次捕获得到的像素，与用于写入 RGB 转换结果的缓冲区；字段/方法
+    /// 名沿用 `rgba`，但两个后端实际存的都是原始 BGRA 字节序（与 Windows
+    /// DXGI `Bgra8` 格式一致），调用方统一按 BGRA 做 RGB 转换。合成一个
+    /// 方法同时借出两者，避免调用方对同一个 `ctx` 做两次互斥借用。
+    fn rgba_rgb_buffers(&mut self) -> (&[u8], &mut Vec<u8>);
+}
+
+/// 一个可供 GUI 下拉框展示的显示器条目。
+pub struct MonitorInfo {
+    pub index: usize,
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+    /// 该显示器左上角在虚拟桌面坐标系中的偏移；非主显示器或排列在主显示器
+    /// 左/上方时可能为负数。覆盖层窗口据此定位，而不是总假设显示器位于
+    /// 虚拟桌面原点。
+    pub x: i32,
+    pub y: i32,
+}
+
+/// Direct3D 11 屏幕捕获上下文（Windows）/ X11 根窗口捕获上下文（其余平台）。
+#[cfg(windows)]
 pub type CaptureContext = inner::CaptureContext;
+#[cfg(not(windows))]
+pub type CaptureContext = x11::X11CaptureContext;
+
+/// 枚举所有已连接显示器，索引与 `create_capture_context` 的
+/// `monitor_index` 参数一一对应。
+pub fn enumerate_monitors() -> anyhow::Result<Vec<MonitorInfo>> {
+    #[cfg(windows)]
+    {
+        inner::enumerate_monitors()
+    }
+    #[cfg(not(windows))]
+    {
+        x11::enumerate_monitors()
+    }
+}
+
+/// 主显示器的像素宽高，供不依赖 `CaptureContext` 实例的调用方
+/// （例如显示器列表尚未枚举出来时，覆盖层窗口的兜底定位）查询屏幕尺寸。
+pub fn primary_monitor_size() -> anyhow::Result<(u32, u32)> {
+    #[cfg(windows)]
+    {
+        inner::primary_monitor_size()
+    }
+    #[cfg(not(windows))]
+    {
+        x11::primary_monitor_size()
+    }
+}
 
-/// 创建捕获上下文
-pub fn create_capture_context(timeout_ms: u32) -> anyhow::Result<CaptureContext> {
-    inner::create_capture_context(timeout_ms)
+/// 创建捕获上下文，`monitor_index` 决定捕获哪块显示器。
+pub fn create_capture_context(monitor_index: usize, timeout_ms: u32) -> anyhow::Result<CaptureContext> {
+    CaptureContext::create(monitor_index, timeout_ms)
 }
 
 /// 捕获全屏
@@ -13,10 +79,10 @@ pub fn create_capture_context(timeout_ms: u32) -> anyhow::Result<CaptureContext>
 /// # 出参
 /// - `bool`: 是否捕获到新帧
 pub fn capture_full(ctx: &mut CaptureContext) -> anyhow::Result<bool> {
-    inner::capture_full(ctx)
+    ctx.capture_full()
 }
 
-/// 捕获屏幕区域
+/// 捕获屏幕区域，坐标相对于所选显示器自身的左上角（而非虚拟桌面原点）。
 ///
 /// # 入参
 /// - `x`, `y`: 区域左上角坐标
@@ -31,5 +97,5 @@ pub fn capture_region(
     width: u32,
     height: u32,
 ) -> anyhow::Result<bool> {
-    inner::capture_region(ctx, x, y, width, height)
+    ctx.capture_region(x, y, width, height)
 }
@@ -0,0 +1,249 @@
+//! Linux 下的屏幕捕获后端：通过 Xlib 对根窗口做 `XGetImage` 截屏。常见
+//! 的 24/32 位 TrueColor 视觉按 BGRX/BGRA 排列；`rgba_buffer` 字段名沿用
+//! `CaptureBackend` trait 的命名，但和 Windows DXGI 后端一样，实际存的是
+//! 原始 BGRA 字节序（第 4 字节一律填成不透明的 255），调用方（如 `InferenceEngine`）统一按 BGRA 做 RGB 转换。需要更高
+//! 帧率时可以把 `XGetImage` 换成 XShm 扩展做零拷贝，接口不受影响。
+//!
+//! 多显示器通过 Xinerama 扩展枚举（`XineramaQueryScreens`）；没有该扩展
+//! 时退化为把整个 X 屏幕当作唯一一块“显示器”。
+
+use anyhow::{bail, Context, Result};
+use std::ptr;
+
+use crate::capture::{CaptureBackend, MonitorInfo};
+
+/// 某块显示器在虚拟桌面坐标系中的几何信息。
+struct MonitorGeometry {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+}
+
+fn query_screens(display: *mut x11::xlib::Display) -> Vec<MonitorGeometry> {
+    use x11::xinerama;
+
+    unsafe {
+        if xinerama::XineramaIsActive(display) == 0 {
+            return Vec::new();
+        }
+        let mut count: i32 = 0;
+        let infos = xinerama::XineramaQueryScreens(display, &mut count);
+        if infos.is_null() {
+            return Vec::new();
+        }
+        let screens = std::slice::from_raw_parts(infos, count as usize)
+            .iter()
+            .map(|s| MonitorGeometry {
+                x: s.x_org as i32,
+                y: s.y_org as i32,
+                width: s.width as u32,
+                height: s.height as u32,
+            })
+            .collect();
+        x11::xlib::XFree(infos as *mut core::ffi::c_void);
+        screens
+    }
+}
+
+fn default_screen_geometry(display: *mut x11::xlib::Display) -> MonitorGeometry {
+    use x11::xlib;
+    unsafe {
+        let screen = xlib::XDefaultScreen(display);
+        MonitorGeometry {
+            x: 0,
+            y: 0,
+            width: xlib::XDisplayWidth(display, screen) as u32,
+            height: xlib::XDisplayHeight(display, screen) as u32,
+        }
+    }
+}
+
+fn select_monitor(display: *mut x11::xlib::Display, monitor_index: usize) -> MonitorGeometry {
+    let mut screens = query_screens(display);
+    if screens.is_empty() {
+        return default_screen_geometry(display);
+    }
+    if monitor_index < screens.len() {
+        screens.remove(monitor_index)
+    } else {
+        screens.remove(0)
+    }
+}
+
+/// 枚举所有已连接显示器，索引与 `X11CaptureContext::create` 的
+/// `monitor_index` 参数一一对应。
+pub fn enumerate_monitors() -> Result<Vec<MonitorInfo>> {
+    use x11::xlib;
+    unsafe {
+        let display = xlib::XOpenDisplay(ptr::null());
+        if display.is_null() {
+            bail!("无法连接 X11 Display，请检查 DISPLAY 环境变量");
+        }
+        let mut screens = query_screens(display);
+        if screens.is_empty() {
+            screens.push(default_screen_geometry(display));
+        }
+        let monitors = screens
+            .into_iter()
+            .enumerate()
+            .map(|(index, geo)| MonitorInfo {
+                index,
+                name: format!("显示器 {}", index + 1),
+                width: geo.width,
+                height: geo.height,
+                x: geo.x,
+                y: geo.y,
+            })
+            .collect();
+        xlib::XCloseDisplay(display);
+        Ok(monitors)
+    }
+}
+
+/// 主显示器（Xinerama 下标 0，否则整个 X 屏幕）的像素宽高。
+pub fn primary_monitor_size() -> Result<(u32, u32)> {
+    let monitors = enumerate_monitors()?;
+    monitors
+        .first()
+        .map(|m| (m.width, m.height))
+        .context("未找到任何显示器")
+}
+
+pub struct X11CaptureContext {
+    display: *mut x11::xlib::Display,
+    screen: i32,
+    monitor_index: usize,
+    /// 所选显示器左上角在虚拟桌面坐标系中的偏移；`capture_full`/
+    /// `capture_region` 的坐标相对这个偏移解释，而不是整个虚拟桌面原点。
+    monitor_x: i32,
+    monitor_y: i32,
+    width: u32,
+    height: u32,
+    pub rgba_buffer: Vec<u8>,
+    pub rgb_buffer: Vec<u8>,
+}
+
+// `Display*` 只在这个进程内由同一个捕获线程创建和使用，不会跨线程共享。
+unsafe impl Send for X11CaptureContext {}
+
+impl X11CaptureContext {
+    fn grab(&mut self, x: i32, y: i32, width: u32, height: u32) -> Result<bool> {
+        use x11::xlib;
+
+        if width == 0 || height == 0 {
+            return Ok(false);
+        }
+
+        unsafe {
+            let root = xlib::XRootWindow(self.display, self.screen);
+            let image = xlib::XGetImage(
+                self.display,
+                root,
+                self.monitor_x + x,
+                self.monitor_y + y,
+                width,
+                height,
+                xlib::XAllPlanes(),
+                xlib::ZPixmap,
+            );
+            if image.is_null() {
+                bail!("XGetImage 截屏失败");
+            }
+
+            let img = &*image;
+            let bytes_per_pixel = (img.bits_per_pixel / 8).max(1) as usize;
+            let stride = img.bytes_per_line as usize;
+            let data = img.data as *const u8;
+
+            // 与 Windows DXGI 后端保持相同的字节序契约：直接存原始 BGRA，
+            // 不在这里转换成真正的 R,G,B,A 顺序，交给下游统一按 BGRA 处理。
+            self.rgba_buffer.clear();
+            self.rgba_buffer.reserve((width * height * 4) as usize);
+            for row in 0..height as usize {
+                let row_start = data.add(row * stride);
+                for col in 0..width as usize {
+                    let pixel = row_start.add(col * bytes_per_pixel);
+                    self.rgba_buffer.push(*pixel); // B
+                    self.rgba_buffer.push(*pixel.add(1)); // G
+                    self.rgba_buffer.push(*pixel.add(2)); // R
+                    self.rgba_buffer.push(255);
+                }
+            }
+
+            xlib::XDestroyImage(image);
+            Ok(!self.rgba_buffer.is_empty())
+        }
+    }
+
+    /// 所选显示器被拔除/重新排列后，重新查询一次几何信息并重新绑定到同一个
+    /// `monitor_index`，与 Windows 后端 `CaptureContext::recreate` 的语义对应。
+    fn recreate(&mut self) -> Result<()> {
+        let geo = select_monitor(self.display, self.monitor_index);
+        self.monitor_x = geo.x;
+        self.monitor_y = geo.y;
+        self.width = geo.width;
+        self.height = geo.height;
+        Ok(())
+    }
+}
+
+impl CaptureBackend for X11CaptureContext {
+    fn create(monitor_index: usize, _timeout_ms: u32) -> Result<Self> {
+        use x11::xlib;
+
+        unsafe {
+            let display = xlib::XOpenDisplay(ptr::null());
+            if display.is_null() {
+                bail!("无法连接 X11 Display，请检查 DISPLAY 环境变量");
+            }
+            let screen = xlib::XDefaultScreen(display);
+            let geo = select_monitor(display, monitor_index);
+            Ok(Self {
+                display,
+                screen,
+                monitor_index,
+                monitor_x: geo.x,
+                monitor_y: geo.y,
+                width: geo.width,
+                height: geo.height,
+                rgba_buffer: Vec::new(),
+                rgb_buffer: Vec::new(),
+            })
+        }
+    }
+
+    fn width(&self) -> u32 {
+        self.width
+    }
+
+    fn height(&self) -> u32 {
+        self.height
+    }
+
+    fn capture_full(&mut self) -> Result<bool> {
+        let (width, height) = (self.width, self.height);
+        self.grab(0, 0, width, height)
+    }
+
+    fn capture_region(&mut self, x: u32, y: u32, width: u32, height: u32) -> Result<bool> {
+        if x >= self.width || y >= self.height {
+            return Ok(false);
+        }
+        let w = width.min(self.width - x);
+        let h = height.min(self.height - y);
+        self.grab(x as i32, y as i32, w, h)
+    }
+
+    fn rgba_rgb_buffers(&mut self) -> (&[u8], &mut Vec<u8>) {
+        (&self.rgba_buffer, &mut self.rgb_buffer)
+    }
+}
+
+impl Drop for X11CaptureContext {
+    fn drop(&mut self) {
+        unsafe {
+            x11::xlib::XCloseDisplay(self.display);
+        }
+    }
+}
@@ -10,14 +10,78 @@ pub enum TriggerType {
     Toggle,
 }
 
+/// 原始 Windows set-1 扫描码，而非经过布局翻译的虚拟键码，
+/// 这样绑定的热键在切换输入法/键盘布局后仍然有效。
+pub type ScanCode = u32;
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+    X1,
+    X2,
+}
+
+/// 热键按下时需要同时满足的修饰键集合。
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct ModifiersState {
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+    pub win: bool,
+}
+
+impl ModifiersState {
+    pub fn is_empty(&self) -> bool {
+        !self.ctrl && !self.shift && !self.alt && !self.win
+    }
+}
+
+/// 一个热键组合：一个触发键（物理按键扫描码或鼠标按键）加上修饰键集合。
+///
+/// 使用扫描码而非虚拟键码匹配，使组合键在非美式键盘布局下依然绑定到同一个
+/// 物理按键位置；`mouse_button` 与 `physical_key` 互斥，由录制流程保证。
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct Hotkey {
+    pub physical_key: Option<ScanCode>,
+    pub mouse_button: Option<MouseButton>,
+    pub modifiers: ModifiersState,
+}
+
+impl Hotkey {
+    pub fn is_unbound(&self) -> bool {
+        self.physical_key.is_none() && self.mouse_button.is_none()
+    }
+}
+
+/// 热键监听后端：`Polling` 沿用旧版固定周期 `GetAsyncKeyState` 轮询；
+/// `LowLevelHook` 改用 `SetWindowsHookEx` 事件驱动，延迟更低但依赖
+/// Win32 低级钩子，仅 Windows 上有效（其余平台退化为 `Polling`）。
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum InputBackendKind {
+    #[default]
+    Polling,
+    LowLevelHook,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ControlSettings {
     pub yaw_sensitivity: f32,
     pub pitch_sensitivity: f32,
-    pub hotkey: String,
+    pub hotkey: Hotkey,
     pub trigger_type: TriggerType,
     pub x_target_offset: f32,
     pub y_target_offset: f32,
+    /// 移动预判系数：按目标估算速度 `v` 提前瞄准 `lead * v`，0 表示不预判。
+    pub lead: f32,
+    /// 缓出系数：每帧只修正剩余偏移的这一比例；1.0 时一次性修正，但单帧
+    /// 位移仍受 `max_step` 限制。
+    pub alpha: f32,
+    /// 单帧最大像素位移，防止过冲或瞬移。
+    pub max_step: f32,
+    /// 热键监听使用的后端。
+    pub input_backend: InputBackendKind,
 }
 
 impl Default for ControlSettings {
@@ -25,10 +89,94 @@ impl Default for ControlSettings {
         Self {
             yaw_sensitivity: 0.3,
             pitch_sensitivity: 0.3,
-            hotkey: "x1".to_string(),
+            hotkey: Hotkey {
+                physical_key: None,
+                mouse_button: Some(MouseButton::X1),
+                modifiers: ModifiersState::default(),
+            },
             trigger_type: TriggerType::Hold,
             x_target_offset: 0.0,
             y_target_offset: 0.0,
+            lead: 0.0,
+            alpha: 1.0,
+            max_step: 200.0,
+            input_backend: InputBackendKind::Polling,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum RegionModeSetting {
+    Fullscreen,
+    Center640,
+    Center1280,
+    Custom,
+}
+
+impl Default for RegionModeSetting {
+    fn default() -> Self {
+        Self::Fullscreen
+    }
+}
+
+/// 各 YOLO 版本导出模型的默认网络输入分辨率；目前统一为方形 640x640，
+/// 预留按版本区分的扩展点。
+pub fn default_input_size(_yolo_version: u8) -> (u32, u32) {
+    (640, 640)
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InferenceSettings {
+    pub device_type: String,
+    pub yolo_version: u8,
+    pub conf_threshold: f32,
+    pub nms_iou_threshold: f32,
+    pub objectness_threshold: f32,
+    /// TensorRT 引擎缓存键中的 FP16 标记；只参与缓存命中判断，不传给
+    /// `InferenceEngine::load_model`，不改变实际构建所用的精度。
+    pub fp16: bool,
+    /// 网络输入宽高；捕获画面会按 letterbox 方式等比缩放+灰边填充到此
+    /// 尺寸再送入推理，检测框会映射回原始画面坐标。
+    pub input_width: u32,
+    pub input_height: u32,
+}
+
+impl Default for InferenceSettings {
+    fn default() -> Self {
+        let yolo_version = 26;
+        let (input_width, input_height) = default_input_size(yolo_version);
+        Self {
+            device_type: "cpu".to_string(),
+            yolo_version,
+            conf_threshold: 0.25,
+            nms_iou_threshold: 0.45,
+            objectness_threshold: 0.25,
+            fp16: false,
+            input_width,
+            input_height,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CaptureSettings {
+    pub mode: RegionModeSetting,
+    pub custom_x: u32,
+    pub custom_y: u32,
+    pub custom_width: u32,
+    pub custom_height: u32,
+    pub monitor_index: usize,
+}
+
+impl Default for CaptureSettings {
+    fn default() -> Self {
+        Self {
+            mode: RegionModeSetting::Fullscreen,
+            custom_x: 0,
+            custom_y: 0,
+            custom_width: 1280,
+            custom_height: 720,
+            monitor_index: 0,
         }
     }
 }
@@ -36,6 +184,10 @@ impl Default for ControlSettings {
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct AppConfig {
     pub control: ControlSettings,
+    pub inference: InferenceSettings,
+    pub capture: CaptureSettings,
+    /// 已保存的压枪/喷点宏，见 `crate::macros`。
+    pub macros: Vec<crate::macros::Macro>,
 }
 
 pub struct ConfigManager {
@@ -45,6 +197,9 @@ pub struct ConfigManager {
 
 impl ConfigManager {
     pub fn new(path: PathBuf) -> Self {
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
         let config = if path.exists() {
             serde_json::from_str(&std::fs::read_to_string(&path).unwrap_or_default())
                 .unwrap_or_default()
@@ -61,11 +216,40 @@ impl ConfigManager {
         self.config.lock().clone()
     }
 
+    fn persist(&self, config: &AppConfig) {
+        if let Ok(json) = serde_json::to_string_pretty(config) {
+            let _ = std::fs::write(&self.path, json);
+        }
+    }
+
     pub fn update_control(&self, control: ControlSettings) {
         let mut config = self.config.lock();
         config.control = control;
-        if let Ok(json) = serde_json::to_string_pretty(&*config) {
-            let _ = std::fs::write(&self.path, json);
-        }
+        self.persist(&config);
+    }
+
+    pub fn update_inference(&self, inference: InferenceSettings) {
+        let mut config = self.config.lock();
+        config.inference = inference;
+        self.persist(&config);
+    }
+
+    pub fn update_capture(&self, capture: CaptureSettings) {
+        let mut config = self.config.lock();
+        config.capture = capture;
+        self.persist(&config);
+    }
+
+    pub fn update_macros(&self, macros: Vec<crate::macros::Macro>) {
+        let mut config = self.config.lock();
+        config.macros = macros;
+        self.persist(&config);
+    }
+
+    /// 一次性保存全部设置，用于应用退出时的兜底持久化。
+    pub fn save_all(&self, config: AppConfig) {
+        let mut guard = self.config.lock();
+        *guard = config;
+        self.persist(&guard);
     }
 }
@@ -0,0 +1,81 @@
+//! TensorRT 引擎构建结果的磁盘缓存：按 `(模型文件哈希, yolo_version, 精度,
+//! 输入分辨率)` 做键，记录某次引擎构建是否已经完成过，避免每次启动都重新
+//! 承受 TensorRT 数分钟的序列化构建耗时。
+//!
+//! 本模块不直接调用 TensorRT/ORT，只维护一份应用层的构建清单；真正的引擎
+//! 构建发生在 `InferenceEngine::load_model` 内部，这里仅在加载前后做
+//! 缓存命中判断与登记，用于给 GUI 提供准确的构建进度提示。
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 描述一次引擎构建所需的全部可变因素；任意一项变化都必须重新构建。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EngineCacheKey {
+    pub model_hash: String,
+    pub yolo_version: u8,
+    pub fp16: bool,
+    pub input_width: u32,
+    pub input_height: u32,
+}
+
+impl EngineCacheKey {
+    fn manifest_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{}",
+            self.model_hash, self.yolo_version, self.fp16, self.input_width, self.input_height
+        )
+    }
+}
+
+/// 对模型文件内容做一次简单的 FNV-1a 哈希，作为缓存键的一部分：文件改了
+/// （重新导出、换了权重）哈希就会变，从而强制重新构建引擎。
+pub fn hash_model_file(path: &Path) -> std::io::Result<String> {
+    let bytes = fs::read(path)?;
+    const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x100000001b3;
+    let mut hash = FNV_OFFSET;
+    for byte in &bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    Ok(format!("{:016x}", hash))
+}
+
+/// 引擎构建清单所在目录；清单只是一份「哪些键已经构建过」的文本记录。
+pub struct EngineCache {
+    manifest_path: PathBuf,
+}
+
+impl EngineCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            manifest_path: dir.into().join("manifest.txt"),
+        }
+    }
+
+    /// 缓存中是否已存在与 `key` 完全匹配的构建记录。
+    pub fn contains(&self, key: &EngineCacheKey) -> bool {
+        let Ok(content) = fs::read_to_string(&self.manifest_path) else {
+            return false;
+        };
+        content.lines().any(|line| line == key.manifest_line())
+    }
+
+    /// 登记一次刚完成的构建，供下次启动复用判断。
+    pub fn record(&self, key: &EngineCacheKey) {
+        if self.contains(key) {
+            return;
+        }
+        if let Some(parent) = self.manifest_path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        let mut content = fs::read_to_string(&self.manifest_path).unwrap_or_default();
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+        content.push_str(&key.manifest_line());
+        content.push('\n');
+        let _ = fs::write(&self.manifest_path, content);
+    }
+}
@@ -3,7 +3,7 @@ use std::time::Instant;
 use anyhow::{bail, Context};
 use parking_lot::Mutex;
 use usls::{Config, Device, DType, DynConf, Image, Model, Task, Y, YOLO};
-use crate::capture::CaptureContext;
+use crate::capture::{CaptureBackend, CaptureContext};
 use rayon::prelude::*;
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -17,6 +17,11 @@ pub enum RegionMode {
 #[derive(Clone, Copy)]
 pub struct CaptureConfig {
     pub mode: RegionMode,
+    /// 与 `create_capture_context` 所用的 `monitor_index` 对应；`capture_once`
+    /// 本身不负责创建 `ctx`，只是记录它是为哪块显示器创建的，便于调用方核对
+    /// `RegionMode::Custom` 等坐标确实是相对该显示器左上角（而非虚拟桌面
+    /// 原点）给出的。
+    pub monitor_index: usize,
 }
 
 pub struct InferenceEngine {
@@ -42,6 +47,7 @@ impl InferenceEngine {
             conf_threshold: Arc::new(Mutex::new(0.25)),
             capture_config: Arc::new(Mutex::new(CaptureConfig {
                 mode: RegionMode::Fullscreen,
+                monitor_index: 0,
             })),
         }
     }
@@ -51,6 +57,8 @@ impl InferenceEngine {
         path: std::path::PathBuf,
         device_type: &str,
         version_override: Option<u8>,
+        input_width: u32,
+        input_height: u32,
     ) -> anyhow::Result<()> {
         if path.as_os_str().is_empty() {
             bail!("模型路径为空");
@@ -90,6 +98,10 @@ impl InferenceEngine {
             .with_model_file(path.to_string_lossy())
             .with_model_device(device)
             .with_model_dtype(dtype)
+            // 网络输入高/宽的动态轴覆盖，与 letterbox 预处理实际缩放到的
+            // 尺寸保持一致，否则 unmap_box 的坐标映射对不上真实推理分辨率。
+            .with_model_ixx(0, 2, input_height as isize)
+            .with_model_ixx(0, 3, input_width as isize)
             .commit()
             .with_context(|| format!("USLS 配置提交失败: path={:?}, device_type={}", path, device_type))?;
 
@@ -99,6 +111,41 @@ impl InferenceEngine {
         Ok(())
     }
 
+    /// 对调用方已经捕获好的一帧画面做推理（参数名沿用 `rgba`，实际按捕获
+    /// 后端的 BGRA 字节序解释）：BGRA -> RGB 转换、构建
+    /// `Image`、送入已加载的模型。网络输入分辨率在 `load_model` 时已经通过
+    /// `Config::with_model_ixx` 写死，usls 在 `model.run` 内部据此对输入
+    /// 图像做 letterbox 缩放，这里不需要也不应该再手动缩放一遍。
+    pub fn infer_with_preprocess(
+        &self,
+        rgba: &[u8],
+        width: u32,
+        height: u32,
+    ) -> anyhow::Result<Vec<Y>> {
+        let mut model_lock = self.model.lock();
+        let (model, engines) = model_lock.as_mut().context("模型尚未加载")?;
+
+        let rgb_len = (width * height * 3) as usize;
+        let mut rgb = vec![0u8; rgb_len];
+        rgb.par_chunks_exact_mut(3)
+            .zip(rgba.par_chunks_exact(4))
+            .for_each(|(rgb_p, rgba_p)| {
+                rgb_p[0] = rgba_p[2]; // R（捕获后端按 BGRA 排列）
+                rgb_p[1] = rgba_p[1]; // G
+                rgb_p[2] = rgba_p[0]; // B
+            });
+
+        let img = Image::from_u8s(&rgb, width, height)?;
+        let conf = *self.conf_threshold.lock();
+        model.confs = DynConf::new_or(&[conf], model.nc, conf);
+        model.run(engines, &[img]).context("YOLO 推理失败")
+    }
+
+    /// 在已创建好的 `ctx` 上捕获并推理一帧。`ctx` 应当已经用
+    /// `self.capture_config.lock().monitor_index` 对应的显示器创建
+    /// （见 `crate::capture::create_capture_context`），这样这里算出的
+    /// `RegionMode::Custom`/`Center640`/`Center1280` 区域坐标才是相对该
+    /// 显示器左上角，而不是相对整个虚拟桌面。
     pub fn capture_once(&self, ctx: &mut CaptureContext) -> anyhow::Result<CaptureStats> {
         if !*self.is_running.lock() {
             return Ok(CaptureStats {
@@ -109,8 +156,8 @@ impl InferenceEngine {
         }
         let capture_start = Instant::now();
         let config = *self.capture_config.lock();
-        let full_w = ctx.width;
-        let full_h = ctx.height;
+        let full_w = ctx.width();
+        let full_h = ctx.height();
 
         let (did_capture, width, height) = match config.mode {
             RegionMode::Fullscreen => {
@@ -145,7 +192,7 @@ impl InferenceEngine {
                 }
             }
         };
-        if !did_capture || ctx.rgba_buffer.is_empty() {
+        if !did_capture || ctx.rgba_rgb_buffers().0.is_empty() {
             return Ok(CaptureStats {
                 did_capture: false,
                 capture_ms: 0.0,
@@ -158,21 +205,19 @@ impl InferenceEngine {
         let mut model_lock = self.model.lock();
         if let Some((model, engines)) = model_lock.as_mut() {
             let rgb_len = (width * height * 3) as usize;
-            ctx.rgb_buffer.resize(rgb_len, 0);
-            
+            let (rgba, rgb) = ctx.rgba_rgb_buffers();
+            rgb.resize(rgb_len, 0);
+
             // 使用 Rayon 并行加速 RGBA -> RGB 转换
-            let rgba = &ctx.rgba_buffer;
-            let rgb = &mut ctx.rgb_buffer;
-            
             rgb.par_chunks_exact_mut(3)
                 .zip(rgba.par_chunks_exact(4))
                 .for_each(|(rgb_p, rgba_p)| {
-                    rgb_p[0] = rgba_p[2]; // R (DXGI is BGRA)
+                    rgb_p[0] = rgba_p[2]; // R（捕获后端按 BGRA 排列）
                     rgb_p[1] = rgba_p[1]; // G
                     rgb_p[2] = rgba_p[0]; // B
                 });
 
-            let img = Image::from_u8s(&ctx.rgb_buffer, width, height)?;
+            let img = Image::from_u8s(ctx.rgba_rgb_buffers().1, width, height)?;
             capture_ms = capture_start.elapsed().as_secs_f64() * 1000.0;
             let conf = *self.conf_threshold.lock();
             model.confs = DynConf::new_or(&[conf], model.nc, conf);
@@ -5,19 +5,36 @@ use std::time::Instant;
 
 use parking_lot::Mutex;
 
+use crate::config::{Hotkey, InputBackendKind, ModifiersState, MouseButton, ScanCode};
+
 #[cfg(windows)]
 use windows::Win32::UI::Input::KeyboardAndMouse::{
-    GetAsyncKeyState, VK_LBUTTON, VK_MBUTTON, VK_RBUTTON, VK_XBUTTON1, VK_XBUTTON2,
+    GetAsyncKeyState, GetKeyNameTextW, MapVirtualKeyW, MAPVK_VK_TO_VSC_EX, MAPVK_VSC_TO_VK_EX,
+    VK_CONTROL, VK_LBUTTON, VK_LWIN, VK_MBUTTON, VK_MENU, VK_RBUTTON, VK_RWIN, VK_SHIFT,
+    VK_XBUTTON1, VK_XBUTTON2,
+};
+
+#[cfg(windows)]
+use windows::Win32::Foundation::{LPARAM, LRESULT, WPARAM};
+#[cfg(windows)]
+use windows::Win32::System::Threading::GetCurrentThreadId;
+#[cfg(windows)]
+use windows::Win32::UI::WindowsAndMessaging::{
+    CallNextHookEx, DispatchMessageW, GetMessageW, PostThreadMessageW, SetWindowsHookExW,
+    TranslateMessage, UnhookWindowsHookEx, MSG, WH_KEYBOARD_LL, WH_MOUSE_LL, WM_QUIT,
 };
 
 pub struct InputListener {
     running: Arc<AtomicBool>,
     hotkey_pressed: Arc<Mutex<bool>>,
-    hotkey_name: Arc<Mutex<String>>,
+    hotkey: Arc<Mutex<Hotkey>>,
     last_trigger: Arc<Mutex<Instant>>,
     capturing: Arc<AtomicBool>,
-    captured_key: Arc<Mutex<Option<String>>>,
+    captured_hotkey: Arc<Mutex<Option<Hotkey>>>,
     toggle_state: Arc<Mutex<bool>>,
+    /// 低级钩子后端所在线程的 id；`stop` 用它投递 `WM_QUIT` 结束消息泵。
+    #[cfg(windows)]
+    hook_thread_id: Arc<Mutex<Option<u32>>>,
 }
 
 impl InputListener {
@@ -26,40 +43,133 @@ impl InputListener {
         Self {
             running: Arc::new(AtomicBool::new(false)),
             hotkey_pressed: Arc::new(Mutex::new(false)),
-            hotkey_name: Arc::new(Mutex::new("x1".to_string())),
+            hotkey: Arc::new(Mutex::new(Hotkey {
+                physical_key: None,
+                mouse_button: Some(MouseButton::X1),
+                modifiers: ModifiersState::default(),
+            })),
             last_trigger: Arc::new(Mutex::new(Instant::now())),
             capturing: Arc::new(AtomicBool::new(false)),
-            captured_key: Arc::new(Mutex::new(None)),
+            captured_hotkey: Arc::new(Mutex::new(None)),
             toggle_state: Arc::new(Mutex::new(false)),
+            #[cfg(windows)]
+            hook_thread_id: Arc::new(Mutex::new(None)),
         }
     }
 
-    pub fn set_hotkey(&self, name: &str) {
-        *self.hotkey_name.lock() = name.to_lowercase();
+    pub fn set_hotkey(&self, hotkey: Hotkey) {
+        *self.hotkey.lock() = hotkey;
+    }
+
+    /// 解析一个形如 `"ctrl+shift+f13"` 的组合键字符串并立即生效；未知 token
+    /// 或组合不合法（例如同时给了鼠标按键和键盘键）时返回错误而不修改当前热键，
+    /// 避免静默绑定到一个永远不会触发的空组合。
+    pub fn set_hotkey_from_accelerator(&self, accelerator: &str) -> Result<(), String> {
+        let hotkey = parse_accelerator(accelerator)?;
+        self.set_hotkey(hotkey);
+        Ok(())
     }
 
-    pub fn start(&self) {
+    /// 与 `set_hotkey_from_accelerator` 相同的解析逻辑，但只返回解析结果
+    /// 不立即生效；供 GUI 在写入 `control_hotkey`/`control_settings` 前校验。
+    pub fn parse_accelerator(accelerator: &str) -> Result<Hotkey, String> {
+        parse_accelerator(accelerator)
+    }
+
+    /// 启动热键监听；`backend` 选择固定周期轮询还是事件驱动的低级钩子
+    /// （仅 Windows 有效，其余平台一律退化为轮询）。
+    pub fn start(&self, backend: InputBackendKind) {
         if self.running.load(Ordering::SeqCst) {
             return;
         }
         self.running.store(true, Ordering::SeqCst);
 
+        match backend {
+            InputBackendKind::Polling => self.start_polling(),
+            InputBackendKind::LowLevelHook => {
+                #[cfg(windows)]
+                {
+                    self.start_hook();
+                }
+                #[cfg(not(windows))]
+                {
+                    self.start_polling();
+                }
+            }
+        }
+    }
+
+    fn start_polling(&self) {
         let running = self.running.clone();
         let hotkey_pressed = self.hotkey_pressed.clone();
-        let hotkey_name = self.hotkey_name.clone();
+        let hotkey = self.hotkey.clone();
 
         thread::spawn(move || {
             while running.load(Ordering::SeqCst) {
-                let name = hotkey_name.lock().clone();
-                let pressed = Self::check_hotkey(&name);
+                let current = hotkey.lock().clone();
+                let pressed = Self::is_chord_down(&current);
                 *hotkey_pressed.lock() = pressed;
                 thread::sleep(std::time::Duration::from_millis(10));
             }
         });
     }
 
+    /// 启动 `SetWindowsHookExW(WH_KEYBOARD_LL/WH_MOUSE_LL)` 事件驱动后端：
+    /// 钩子线程安装全局钩子后跑消息泵，回调每次按键/鼠标事件触发时都会
+    /// 重新核对当前热键组合是否按下，不再需要固定周期轮询。
+    #[cfg(windows)]
+    fn start_hook(&self) {
+        let hotkey = self.hotkey.clone();
+        let hotkey_pressed = self.hotkey_pressed.clone();
+        let hook_thread_id = self.hook_thread_id.clone();
+        let (tx, rx) = std::sync::mpsc::channel::<u32>();
+
+        thread::spawn(move || {
+            *HOOK_STATE.lock() = Some(HookSharedState {
+                hotkey,
+                hotkey_pressed,
+            });
+
+            unsafe {
+                let thread_id = GetCurrentThreadId();
+                let _ = tx.send(thread_id);
+
+                let keyboard_hook =
+                    SetWindowsHookExW(WH_KEYBOARD_LL, Some(keyboard_hook_proc), None, 0);
+                let mouse_hook = SetWindowsHookExW(WH_MOUSE_LL, Some(mouse_hook_proc), None, 0);
+
+                let mut msg = MSG::default();
+                while GetMessageW(&mut msg, None, 0, 0).as_bool() {
+                    let _ = TranslateMessage(&msg);
+                    DispatchMessageW(&msg);
+                }
+
+                if let Ok(hook) = keyboard_hook {
+                    let _ = UnhookWindowsHookEx(hook);
+                }
+                if let Ok(hook) = mouse_hook {
+                    let _ = UnhookWindowsHookEx(hook);
+                }
+            }
+
+            *HOOK_STATE.lock() = None;
+        });
+
+        if let Ok(thread_id) = rx.recv() {
+            *hook_thread_id.lock() = Some(thread_id);
+        }
+    }
+
     pub fn stop(&self) {
         self.running.store(false, Ordering::SeqCst);
+        #[cfg(windows)]
+        {
+            if let Some(thread_id) = self.hook_thread_id.lock().take() {
+                unsafe {
+                    let _ = PostThreadMessageW(thread_id, WM_QUIT, WPARAM(0), LPARAM(0));
+                }
+            }
+        }
     }
 
     pub fn is_hotkey_pressed(&self) -> bool {
@@ -76,6 +186,9 @@ impl InputListener {
         *self.toggle_state.lock() = state;
     }
 
+    /// 供未来需要独立节流的离散触发动作（例如开火）使用；瞄准/平滑的
+    /// 每帧更新不应该挂在这个冷却上，否则会被拖到冷却周期的频率。
+    #[allow(dead_code)]
     pub fn check_trigger_cooldown(&self, cooldown_ms: u64) -> bool {
         let last = *self.last_trigger.lock();
         if last.elapsed().as_millis() as u64 >= cooldown_ms {
@@ -86,17 +199,44 @@ impl InputListener {
         }
     }
 
+    /// 开始录制一个新的热键组合：持续采样按下的修饰键与触发键/按钮，
+    /// 直到所有按键都被释放后冻结整个组合。
     pub fn start_capture(&self) {
         self.capturing.store(true, Ordering::SeqCst);
-        *self.captured_key.lock() = None;
+        *self.captured_hotkey.lock() = None;
 
         let capturing = self.capturing.clone();
-        let captured_key = self.captured_key.clone();
+        let captured_hotkey = self.captured_hotkey.clone();
 
         thread::spawn(move || {
+            let mut seen_modifiers = ModifiersState::default();
+            let mut seen_key: Option<ScanCode> = None;
+            let mut seen_button: Option<MouseButton> = None;
+            let mut ever_pressed = false;
+
             while capturing.load(Ordering::SeqCst) {
-                if let Some(key) = Self::capture_any_key() {
-                    *captured_key.lock() = Some(key);
+                let modifiers = Self::current_modifiers();
+                let (key, button) = Self::current_trigger();
+
+                let anything_down = key.is_some() || button.is_some() || !modifiers.is_empty();
+                if anything_down {
+                    ever_pressed = true;
+                    seen_modifiers.ctrl |= modifiers.ctrl;
+                    seen_modifiers.shift |= modifiers.shift;
+                    seen_modifiers.alt |= modifiers.alt;
+                    seen_modifiers.win |= modifiers.win;
+                    if key.is_some() {
+                        seen_key = key;
+                    }
+                    if button.is_some() {
+                        seen_button = button;
+                    }
+                } else if ever_pressed {
+                    *captured_hotkey.lock() = Some(Hotkey {
+                        physical_key: seen_key,
+                        mouse_button: seen_button,
+                        modifiers: seen_modifiers,
+                    });
                     capturing.store(false, Ordering::SeqCst);
                     break;
                 }
@@ -109,8 +249,8 @@ impl InputListener {
         self.capturing.store(false, Ordering::SeqCst);
     }
 
-    pub fn get_captured_key(&self) -> Option<String> {
-        self.captured_key.lock().clone()
+    pub fn get_captured_hotkey(&self) -> Option<Hotkey> {
+        self.captured_hotkey.lock().clone()
     }
 
     #[allow(dead_code)]
@@ -118,58 +258,353 @@ impl InputListener {
         self.capturing.load(Ordering::SeqCst)
     }
 
-    #[allow(dead_code)]
+    /// 录制过程中采样当前按下的修饰键状态。
+    #[cfg(windows)]
+    fn current_modifiers() -> ModifiersState {
+        unsafe {
+            ModifiersState {
+                ctrl: GetAsyncKeyState(VK_CONTROL.0 as i32) as u16 & 0x8000 != 0,
+                shift: GetAsyncKeyState(VK_SHIFT.0 as i32) as u16 & 0x8000 != 0,
+                alt: GetAsyncKeyState(VK_MENU.0 as i32) as u16 & 0x8000 != 0,
+                win: GetAsyncKeyState(VK_LWIN.0 as i32) as u16 & 0x8000 != 0
+                    || GetAsyncKeyState(VK_RWIN.0 as i32) as u16 & 0x8000 != 0,
+            }
+        }
+    }
+
+    #[cfg(not(windows))]
+    fn current_modifiers() -> ModifiersState {
+        ModifiersState::default()
+    }
+
+    /// 录制过程中采样当前按下的非修饰触发键：鼠标按键优先，否则扫描
+    /// 0x08-0xFE 范围内任意虚拟键，转换成物理扫描码返回。
     #[cfg(windows)]
-    fn capture_any_key() -> Option<String> {
+    fn current_trigger() -> (Option<ScanCode>, Option<MouseButton>) {
         unsafe {
             if GetAsyncKeyState(VK_LBUTTON.0 as i32) as u16 & 0x8000 != 0 {
-                return Some("left".to_string());
+                return (None, Some(MouseButton::Left));
             }
             if GetAsyncKeyState(VK_RBUTTON.0 as i32) as u16 & 0x8000 != 0 {
-                return Some("right".to_string());
+                return (None, Some(MouseButton::Right));
             }
             if GetAsyncKeyState(VK_MBUTTON.0 as i32) as u16 & 0x8000 != 0 {
-                return Some("middle".to_string());
+                return (None, Some(MouseButton::Middle));
             }
             if GetAsyncKeyState(VK_XBUTTON1.0 as i32) as u16 & 0x8000 != 0 {
-                return Some("x1".to_string());
+                return (None, Some(MouseButton::X1));
             }
             if GetAsyncKeyState(VK_XBUTTON2.0 as i32) as u16 & 0x8000 != 0 {
-                return Some("x2".to_string());
+                return (None, Some(MouseButton::X2));
             }
-            None
+            for vk in 0x08u32..=0xFE {
+                if is_modifier_vk(vk) {
+                    continue;
+                }
+                if GetAsyncKeyState(vk as i32) as u16 & 0x8000 != 0 {
+                    let scancode = MapVirtualKeyW(vk, MAPVK_VK_TO_VSC_EX);
+                    if scancode != 0 {
+                        return (Some(scancode), None);
+                    }
+                }
+            }
+            (None, None)
         }
     }
 
     #[cfg(not(windows))]
-    fn capture_any_key() -> Option<String> {
-        None
+    fn current_trigger() -> (Option<ScanCode>, Option<MouseButton>) {
+        (None, None)
+    }
+
+    /// 判断一个完整组合（修饰键 + 触发键/按钮）当前是否处于按下状态。
+    /// 修饰键要求精确匹配，避免误触发；触发源统一走 `TriggerSource::is_down`，
+    /// 使键盘按键与鼠标按键共用同一条轮询路径。
+    fn is_chord_down(hotkey: &Hotkey) -> bool {
+        if hotkey.is_unbound() {
+            return false;
+        }
+        if Self::current_modifiers() != hotkey.modifiers {
+            return false;
+        }
+        TriggerSource::from_hotkey(hotkey)
+            .map(TriggerSource::is_down)
+            .unwrap_or(false)
     }
 
+    /// 仅用于 GUI 标签显示：将扫描码翻译成本地化按键名称。
     #[cfg(windows)]
-    fn check_hotkey(name: &str) -> bool {
+    pub fn scancode_display_name(scancode: ScanCode) -> String {
+        let extended = scancode & 0xE000 != 0;
+        let lparam = ((scancode & 0x00FF) << 16) | if extended { 1 << 24 } else { 0 };
+        let mut buf = [0u16; 64];
         unsafe {
-            match name {
-                "x1" | "mouse_x1" | "侧键1" => {
-                    GetAsyncKeyState(VK_XBUTTON1.0 as i32) as u16 & 0x8000 != 0
+            let len = GetKeyNameTextW(lparam as i32, &mut buf);
+            if len > 0 {
+                return String::from_utf16_lossy(&buf[..len as usize]);
+            }
+        }
+        format!("扫描码 0x{:02X}", scancode)
+    }
+
+    #[cfg(not(windows))]
+    pub fn scancode_display_name(scancode: ScanCode) -> String {
+        format!("扫描码 0x{:02X}", scancode)
+    }
+
+    /// 将热键组合渲染成按钮上显示的文本，如 "Ctrl+Shift+鼠标侧键1"。
+    pub fn describe_hotkey(hotkey: &Hotkey) -> String {
+        let mut parts = Vec::new();
+        if hotkey.modifiers.ctrl {
+            parts.push("Ctrl".to_string());
+        }
+        if hotkey.modifiers.shift {
+            parts.push("Shift".to_string());
+        }
+        if hotkey.modifiers.alt {
+            parts.push("Alt".to_string());
+        }
+        if hotkey.modifiers.win {
+            parts.push("Win".to_string());
+        }
+        if let Some(button) = hotkey.mouse_button {
+            parts.push(
+                match button {
+                    MouseButton::Left => "鼠标左键",
+                    MouseButton::Right => "鼠标右键",
+                    MouseButton::Middle => "鼠标中键",
+                    MouseButton::X1 => "鼠标侧键1",
+                    MouseButton::X2 => "鼠标侧键2",
                 }
-                "x2" | "mouse_x2" | "侧键2" => {
-                    GetAsyncKeyState(VK_XBUTTON2.0 as i32) as u16 & 0x8000 != 0
+                .to_string(),
+            );
+        } else if let Some(scancode) = hotkey.physical_key {
+            parts.push(Self::scancode_display_name(scancode));
+        } else {
+            parts.push("未设置".to_string());
+        }
+        parts.join("+")
+    }
+}
+
+/// 统一的触发源：键盘物理按键与鼠标按键通过同一个事件查询面
+/// (`is_down`) 暴露，这样上层的 Hold/Toggle 触发逻辑无需关心
+/// 绑定的究竟是键盘键还是鼠标侧键。
+enum TriggerSource {
+    Key(ScanCode),
+    Mouse(MouseButton),
+}
+
+impl TriggerSource {
+    fn from_hotkey(hotkey: &Hotkey) -> Option<Self> {
+        if let Some(button) = hotkey.mouse_button {
+            return Some(Self::Mouse(button));
+        }
+        hotkey.physical_key.map(Self::Key)
+    }
+
+    #[cfg(windows)]
+    fn is_down(self) -> bool {
+        unsafe {
+            let vk = match self {
+                TriggerSource::Mouse(MouseButton::Left) => VK_LBUTTON.0,
+                TriggerSource::Mouse(MouseButton::Right) => VK_RBUTTON.0,
+                TriggerSource::Mouse(MouseButton::Middle) => VK_MBUTTON.0,
+                TriggerSource::Mouse(MouseButton::X1) => VK_XBUTTON1.0,
+                TriggerSource::Mouse(MouseButton::X2) => VK_XBUTTON2.0,
+                TriggerSource::Key(scancode) => {
+                    let vk = MapVirtualKeyW(scancode, MAPVK_VSC_TO_VK_EX);
+                    if vk == 0 {
+                        return false;
+                    }
+                    vk
                 }
-                "left" | "左键" => GetAsyncKeyState(VK_LBUTTON.0 as i32) as u16 & 0x8000 != 0,
-                "right" | "右键" => GetAsyncKeyState(VK_RBUTTON.0 as i32) as u16 & 0x8000 != 0,
-                "middle" | "中键" => GetAsyncKeyState(VK_MBUTTON.0 as i32) as u16 & 0x8000 != 0,
-                _ => false,
-            }
+            };
+            GetAsyncKeyState(vk as i32) as u16 & 0x8000 != 0
         }
     }
 
     #[cfg(not(windows))]
-    fn check_hotkey(_name: &str) -> bool {
+    fn is_down(self) -> bool {
         false
     }
 }
 
+#[cfg(windows)]
+fn is_modifier_vk(vk: u32) -> bool {
+    matches!(
+        vk,
+        0x10 | 0x11 | 0x12 | 0xA0 | 0xA1 | 0xA2 | 0xA3 | 0xA4 | 0xA5 | 0x5B | 0x5C
+    )
+}
+
+/// 把 `"ctrl+shift+f13"` 这样按 `+` 切分的组合键字符串解析为 `Hotkey`。
+/// `ctrl`/`control`/`shift`/`alt`/`win`/`super`/`meta` 按修饰键处理（大小写
+/// 不敏感），其余 token 必须恰好一个，且鼠标按键与键盘键不能混用。
+fn parse_accelerator(accelerator: &str) -> Result<Hotkey, String> {
+    let mut modifiers = ModifiersState::default();
+    let mut mouse_button: Option<MouseButton> = None;
+    let mut keyboard_vk: Option<u32> = None;
+
+    for raw_token in accelerator.split('+') {
+        let token = raw_token.trim().to_ascii_lowercase();
+        if token.is_empty() {
+            continue;
+        }
+        match token.as_str() {
+            "ctrl" | "control" => modifiers.ctrl = true,
+            "shift" => modifiers.shift = true,
+            "alt" => modifiers.alt = true,
+            "win" | "super" | "meta" => modifiers.win = true,
+            _ => {
+                if mouse_button.is_some() || keyboard_vk.is_some() {
+                    return Err(format!("组合键只能包含一个非修饰触发键: {}", accelerator));
+                }
+                if let Some(button) = parse_mouse_token(&token) {
+                    mouse_button = Some(button);
+                } else if let Some(vk) = keyboard_token_to_vk(&token) {
+                    keyboard_vk = Some(vk);
+                } else {
+                    return Err(format!("无法识别的按键: {}", raw_token));
+                }
+            }
+        }
+    }
+
+    if let Some(button) = mouse_button {
+        return Ok(Hotkey {
+            physical_key: None,
+            mouse_button: Some(button),
+            modifiers,
+        });
+    }
+
+    let vk = keyboard_vk.ok_or_else(|| format!("组合键缺少非修饰触发键: {}", accelerator))?;
+    let scancode = vk_to_scancode(vk)
+        .ok_or_else(|| format!("按键无法映射到扫描码: {}", accelerator))?;
+    Ok(Hotkey {
+        physical_key: Some(scancode),
+        mouse_button: None,
+        modifiers,
+    })
+}
+
+fn parse_mouse_token(token: &str) -> Option<MouseButton> {
+    match token {
+        "left" => Some(MouseButton::Left),
+        "right" => Some(MouseButton::Right),
+        "middle" => Some(MouseButton::Middle),
+        "x1" => Some(MouseButton::X1),
+        "x2" => Some(MouseButton::X2),
+        _ => None,
+    }
+}
+
+/// 把键名 token 映射到虚拟键码：单个字母/数字直接用其 ASCII 码
+/// （Win32 的 `VK_0`-`VK_9`、`VK_A`-`VK_Z` 恰好等于对应 ASCII 值），
+/// `f1`-`f24` 按公式换算，其余为常见的具名键与标点键。
+fn keyboard_token_to_vk(token: &str) -> Option<u32> {
+    if let Some(rest) = token.strip_prefix('f') {
+        if let Ok(n) = rest.parse::<u32>() {
+            if (1..=12).contains(&n) {
+                return Some(0x70 + (n - 1));
+            }
+            if (13..=24).contains(&n) {
+                return Some(0x7C + (n - 13));
+            }
+        }
+    }
+    if token.len() == 1 {
+        let c = token.chars().next().unwrap();
+        if c.is_ascii_alphanumeric() {
+            return Some(c.to_ascii_uppercase() as u32);
+        }
+    }
+    match token {
+        "space" => Some(0x20),
+        "enter" | "return" => Some(0x0D),
+        "esc" | "escape" => Some(0x1B),
+        "tab" => Some(0x09),
+        "backspace" => Some(0x08),
+        "capslock" => Some(0x14),
+        "insert" | "ins" => Some(0x2D),
+        "delete" | "del" => Some(0x2E),
+        "home" => Some(0x24),
+        "end" => Some(0x23),
+        "pageup" | "pgup" => Some(0x21),
+        "pagedown" | "pgdn" => Some(0x22),
+        "arrowup" => Some(0x26),
+        "arrowdown" => Some(0x28),
+        "arrowleft" => Some(0x25),
+        "arrowright" => Some(0x27),
+        ";" | "semicolon" => Some(0xBA),
+        "=" | "equal" => Some(0xBB),
+        "," | "comma" => Some(0xBC),
+        "-" | "minus" => Some(0xBD),
+        "." | "period" => Some(0xBE),
+        "/" | "slash" => Some(0xBF),
+        "`" | "grave" => Some(0xC0),
+        "[" => Some(0xDB),
+        "\\" | "backslash" => Some(0xDC),
+        "]" => Some(0xDD),
+        "'" | "quote" => Some(0xDE),
+        _ => None,
+    }
+}
+
+/// 低级钩子回调需要访问的监听器状态；`extern "system"` 回调是裸函数指针，
+/// 无法携带闭包捕获，因此用一个全局静态暂存当前监听器的共享句柄。
+#[cfg(windows)]
+struct HookSharedState {
+    hotkey: Arc<Mutex<Hotkey>>,
+    hotkey_pressed: Arc<Mutex<bool>>,
+}
+
+#[cfg(windows)]
+static HOOK_STATE: Mutex<Option<HookSharedState>> = Mutex::new(None);
+
+/// 键盘/鼠标钩子回调共用的处理逻辑：任意一次按键/按钮事件都直接重新核对
+/// 当前配置的组合键是否处于按下状态，而不是尝试在回调里增量维护状态，
+/// 这样与旧版轮询路径共用同一套 `is_chord_down` 判断，行为完全一致。
+#[cfg(windows)]
+fn refresh_hotkey_pressed() {
+    if let Some(state) = HOOK_STATE.lock().as_ref() {
+        let hotkey = state.hotkey.lock().clone();
+        let pressed = InputListener::is_chord_down(&hotkey);
+        *state.hotkey_pressed.lock() = pressed;
+    }
+}
+
+#[cfg(windows)]
+unsafe extern "system" fn keyboard_hook_proc(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    if code >= 0 {
+        refresh_hotkey_pressed();
+    }
+    unsafe { CallNextHookEx(None, code, wparam, lparam) }
+}
+
+#[cfg(windows)]
+unsafe extern "system" fn mouse_hook_proc(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    if code >= 0 {
+        refresh_hotkey_pressed();
+    }
+    unsafe { CallNextHookEx(None, code, wparam, lparam) }
+}
+
+/// 虚拟键码转扫描码；非 Windows 平台下含键盘键的组合键永远解析失败
+/// （只含鼠标按键的组合不经过这里，仍可解析），与
+/// `current_trigger`/`is_down` 等函数在非 Windows 下的占位行为一致。
+#[cfg(windows)]
+fn vk_to_scancode(vk: u32) -> Option<u32> {
+    let scancode = unsafe { MapVirtualKeyW(vk, MAPVK_VK_TO_VSC_EX) };
+    if scancode == 0 { None } else { Some(scancode) }
+}
+
+#[cfg(not(windows))]
+fn vk_to_scancode(_vk: u32) -> Option<u32> {
+    None
+}
+
 impl Default for InputListener {
     fn default() -> Self {
         Self::new()
@@ -0,0 +1,38 @@
+//! 字母箱（letterbox）预处理的纯数学部分：按长边等比缩放以保持原始画面
+//! 长宽比，不足部分用灰边填充到网络输入尺寸，并提供把检测框坐标从网络
+//! 输入空间映射回原始画面空间的逆变换。
+
+/// 一次 letterbox 变换的缩放比例与内边距（单位：网络输入空间像素）。
+#[derive(Clone, Copy, Debug)]
+pub struct LetterboxTransform {
+    pub scale: f32,
+    pub pad_x: f32,
+    pub pad_y: f32,
+}
+
+/// 计算把 `src_w x src_h` 的画面等比缩放后居中放入 `net_w x net_h` 网络
+/// 输入所需的缩放比例与内边距；缩放比例取较小的轴向比例，保证画面整体
+/// 都能放进网络输入尺寸内，未填满的部分留给灰边。
+pub fn compute_transform(src_w: u32, src_h: u32, net_w: u32, net_h: u32) -> LetterboxTransform {
+    let scale = (net_w as f32 / src_w as f32).min(net_h as f32 / src_h as f32);
+    let scaled_w = src_w as f32 * scale;
+    let scaled_h = src_h as f32 * scale;
+    LetterboxTransform {
+        scale,
+        pad_x: (net_w as f32 - scaled_w) / 2.0,
+        pad_y: (net_h as f32 - scaled_h) / 2.0,
+    }
+}
+
+impl LetterboxTransform {
+    /// 把一个网络输入空间下的检测框 `[xmin, ymin, xmax, ymax]` 映射回
+    /// 原始画面空间坐标。
+    pub fn unmap_box(&self, bbox: [f32; 4]) -> [f32; 4] {
+        [
+            (bbox[0] - self.pad_x) / self.scale,
+            (bbox[1] - self.pad_y) / self.scale,
+            (bbox[2] - self.pad_x) / self.scale,
+            (bbox[3] - self.pad_y) / self.scale,
+        ]
+    }
+}
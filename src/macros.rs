@@ -0,0 +1,325 @@
+//! 宏录制与回放：录制一段带微秒级帧间延迟的输入事件序列（鼠标相对位移、
+//! 鼠标按钮与键盘按键的按下/抬起），交给 `ConfigManager` 序列化持久化；
+//! 回放时按录制节奏把鼠标位移重放给 `mouse_control::move_relative`，按键
+//! /按钮通过 `SendInput` 注入，可整体加速/减速并循环播放。
+//!
+//! 典型用途是录制一次固定的后坐力压枪/喷点位移序列，之后在瞄准热键按下
+//! 时自动、确定性地重放，而不依赖每次手动操作。
+
+use crate::config::{MouseButton, ScanCode};
+use crate::mouse_control::{get_cursor_position, move_relative};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[cfg(windows)]
+use windows::Win32::UI::Input::KeyboardAndMouse::{
+    GetAsyncKeyState, MapVirtualKeyW, SendInput, INPUT, INPUT_0, INPUT_KEYBOARD, INPUT_MOUSE,
+    KEYBDINPUT, KEYEVENTF_KEYUP, KEYEVENTF_SCANCODE, MAPVK_VK_TO_VSC_EX, MAPVK_VSC_TO_VK_EX,
+    MOUSEEVENTF_LEFTDOWN, MOUSEEVENTF_LEFTUP, MOUSEEVENTF_MIDDLEDOWN, MOUSEEVENTF_MIDDLEUP,
+    MOUSEEVENTF_RIGHTDOWN, MOUSEEVENTF_RIGHTUP, MOUSEEVENTF_XDOWN, MOUSEEVENTF_XUP, MOUSEINPUT,
+    VIRTUAL_KEY, VK_LBUTTON, VK_MBUTTON, VK_RBUTTON, VK_XBUTTON1, VK_XBUTTON2, XBUTTON1, XBUTTON2,
+};
+
+/// 宏中的单个动作：鼠标相对位移，或键盘/鼠标按钮的按下、抬起。
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum MacroAction {
+    MouseMove { dx: i32, dy: i32 },
+    MouseButtonDown(MouseButton),
+    MouseButtonUp(MouseButton),
+    KeyDown(ScanCode),
+    KeyUp(ScanCode),
+}
+
+/// 一条录制事件：距上一条事件的延迟（微秒）+ 动作本身。
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct MacroEvent {
+    pub delay_us: u64,
+    pub action: MacroAction,
+}
+
+/// 一段命名的录制序列，整体通过 `ConfigManager` 序列化为 JSON 持久化。
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct Macro {
+    pub name: String,
+    pub events: Vec<MacroEvent>,
+}
+
+/// 宏录制器：`start_recording`/`stop_recording` 之间持续采样鼠标位移、
+/// 鼠标按钮与键盘按键的状态变化，生成一份 `Macro`；同时持有播放冷却，
+/// 供调用方像 `InputListener::check_trigger_cooldown` 一样防止连续误触发
+/// 重复播放。
+pub struct MacroRecorder {
+    recording: Arc<AtomicBool>,
+    events: Arc<Mutex<Vec<MacroEvent>>>,
+    last_play: Arc<Mutex<Instant>>,
+}
+
+impl MacroRecorder {
+    pub fn new() -> Self {
+        Self {
+            recording: Arc::new(AtomicBool::new(false)),
+            events: Arc::new(Mutex::new(Vec::new())),
+            last_play: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording.load(Ordering::SeqCst)
+    }
+
+    /// 开始录制：后台线程以 1ms 周期采样鼠标位移与按键/按钮状态变化，
+    /// 每次变化都记录距上一次变化的真实延迟，重放时据此还原节奏。
+    pub fn start_recording(&self) {
+        if self.recording.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        *self.events.lock() = Vec::new();
+
+        let recording = self.recording.clone();
+        let events = self.events.clone();
+
+        thread::spawn(move || {
+            let mut last_time = Instant::now();
+            let mut last_pos = get_cursor_position().unwrap_or((0, 0));
+            let mut pressed_buttons: HashSet<MouseButton> = HashSet::new();
+            let mut pressed_keys: HashSet<ScanCode> = HashSet::new();
+
+            while recording.load(Ordering::SeqCst) {
+                let now = Instant::now();
+
+                let pos = get_cursor_position().unwrap_or(last_pos);
+                if pos != last_pos {
+                    let dx = pos.0 - last_pos.0;
+                    let dy = pos.1 - last_pos.1;
+                    record(&events, &mut last_time, now, MacroAction::MouseMove { dx, dy });
+                    last_pos = pos;
+                }
+
+                for button in [
+                    MouseButton::Left,
+                    MouseButton::Right,
+                    MouseButton::Middle,
+                    MouseButton::X1,
+                    MouseButton::X2,
+                ] {
+                    let down = mouse_button_down(button);
+                    let was_down = pressed_buttons.contains(&button);
+                    if down && !was_down {
+                        record(&events, &mut last_time, now, MacroAction::MouseButtonDown(button));
+                        pressed_buttons.insert(button);
+                    } else if !down && was_down {
+                        record(&events, &mut last_time, now, MacroAction::MouseButtonUp(button));
+                        pressed_buttons.remove(&button);
+                    }
+                }
+
+                for scancode in pressed_scancodes() {
+                    if pressed_keys.insert(scancode) {
+                        record(&events, &mut last_time, now, MacroAction::KeyDown(scancode));
+                    }
+                }
+                pressed_keys.retain(|scancode| {
+                    let still_down = key_down_by_scancode(*scancode);
+                    if !still_down {
+                        record(&events, &mut last_time, now, MacroAction::KeyUp(*scancode));
+                    }
+                    still_down
+                });
+
+                thread::sleep(Duration::from_millis(1));
+            }
+        });
+    }
+
+    /// 结束录制，把已采样的事件打包成一份命名的 `Macro`。
+    pub fn stop_recording(&self, name: impl Into<String>) -> Macro {
+        self.recording.store(false, Ordering::SeqCst);
+        Macro {
+            name: name.into(),
+            events: self.events.lock().clone(),
+        }
+    }
+
+    /// 与 `InputListener::check_trigger_cooldown` 同样的节流判断，避免热键
+    /// 按住不放时同一个宏被连续重复触发播放。
+    pub fn check_play_cooldown(&self, cooldown_ms: u64) -> bool {
+        let last = *self.last_play.lock();
+        if last.elapsed().as_millis() as u64 >= cooldown_ms {
+            *self.last_play.lock() = Instant::now();
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl Default for MacroRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn record(
+    events: &Arc<Mutex<Vec<MacroEvent>>>,
+    last_time: &mut Instant,
+    now: Instant,
+    action: MacroAction,
+) {
+    let delay_us = now.duration_since(*last_time).as_micros() as u64;
+    events.lock().push(MacroEvent { delay_us, action });
+    *last_time = now;
+}
+
+/// 按录制节奏回放一段宏：鼠标位移交给 `move_relative`，按钮/按键通过
+/// `SendInput` 注入。`speed_multiplier` 整体缩放每条事件的延迟（延迟除以
+/// 该值：大于 1 更快、小于 1 更慢），`loop_count` 为 0 时按 1 次处理，否则循环播放
+/// 对应次数；`running` 置为 `false` 时可随时提前中止（复用检测线程自己
+/// 的运行标志，停止检测即可一并打断播放）。
+pub fn play(macro_: &Macro, speed_multiplier: f32, loop_count: u32, running: &AtomicBool) {
+    let speed = speed_multiplier.max(0.01);
+    let iterations = loop_count.max(1);
+    for _ in 0..iterations {
+        if !running.load(Ordering::SeqCst) {
+            return;
+        }
+        for event in &macro_.events {
+            if !running.load(Ordering::SeqCst) {
+                return;
+            }
+            let delay = Duration::from_micros((event.delay_us as f32 / speed) as u64);
+            if delay > Duration::ZERO {
+                thread::sleep(delay);
+            }
+            match event.action {
+                MacroAction::MouseMove { dx, dy } => move_relative(dx, dy),
+                MacroAction::MouseButtonDown(button) => send_mouse_button(button, true),
+                MacroAction::MouseButtonUp(button) => send_mouse_button(button, false),
+                MacroAction::KeyDown(scancode) => send_key(scancode, true),
+                MacroAction::KeyUp(scancode) => send_key(scancode, false),
+            }
+        }
+    }
+}
+
+#[cfg(windows)]
+fn mouse_button_down(button: MouseButton) -> bool {
+    let vk = match button {
+        MouseButton::Left => VK_LBUTTON.0,
+        MouseButton::Right => VK_RBUTTON.0,
+        MouseButton::Middle => VK_MBUTTON.0,
+        MouseButton::X1 => VK_XBUTTON1.0,
+        MouseButton::X2 => VK_XBUTTON2.0,
+    };
+    unsafe { GetAsyncKeyState(vk as i32) as u16 & 0x8000 != 0 }
+}
+
+#[cfg(not(windows))]
+fn mouse_button_down(_button: MouseButton) -> bool {
+    false
+}
+
+/// 扫描 0x08-0xFE 范围内当前按下的虚拟键，转换成物理扫描码返回；与
+/// `InputListener::current_trigger` 使用同一套扫描范围与转换方式。
+#[cfg(windows)]
+fn pressed_scancodes() -> Vec<ScanCode> {
+    let mut result = Vec::new();
+    unsafe {
+        for vk in 0x08u32..=0xFE {
+            if GetAsyncKeyState(vk as i32) as u16 & 0x8000 != 0 {
+                let scancode = MapVirtualKeyW(vk, MAPVK_VK_TO_VSC_EX);
+                if scancode != 0 {
+                    result.push(scancode);
+                }
+            }
+        }
+    }
+    result
+}
+
+#[cfg(not(windows))]
+fn pressed_scancodes() -> Vec<ScanCode> {
+    Vec::new()
+}
+
+#[cfg(windows)]
+fn key_down_by_scancode(scancode: ScanCode) -> bool {
+    unsafe {
+        let vk = MapVirtualKeyW(scancode, MAPVK_VSC_TO_VK_EX);
+        if vk == 0 {
+            return false;
+        }
+        GetAsyncKeyState(vk as i32) as u16 & 0x8000 != 0
+    }
+}
+
+#[cfg(not(windows))]
+fn key_down_by_scancode(_scancode: ScanCode) -> bool {
+    false
+}
+
+#[cfg(windows)]
+fn send_mouse_button(button: MouseButton, down: bool) {
+    let (flag, mouse_data) = match (button, down) {
+        (MouseButton::Left, true) => (MOUSEEVENTF_LEFTDOWN, 0),
+        (MouseButton::Left, false) => (MOUSEEVENTF_LEFTUP, 0),
+        (MouseButton::Right, true) => (MOUSEEVENTF_RIGHTDOWN, 0),
+        (MouseButton::Right, false) => (MOUSEEVENTF_RIGHTUP, 0),
+        (MouseButton::Middle, true) => (MOUSEEVENTF_MIDDLEDOWN, 0),
+        (MouseButton::Middle, false) => (MOUSEEVENTF_MIDDLEUP, 0),
+        (MouseButton::X1, true) => (MOUSEEVENTF_XDOWN, XBUTTON1),
+        (MouseButton::X1, false) => (MOUSEEVENTF_XUP, XBUTTON1),
+        (MouseButton::X2, true) => (MOUSEEVENTF_XDOWN, XBUTTON2),
+        (MouseButton::X2, false) => (MOUSEEVENTF_XUP, XBUTTON2),
+    };
+    let input = INPUT {
+        r#type: INPUT_MOUSE,
+        Anonymous: INPUT_0 {
+            mi: MOUSEINPUT {
+                dx: 0,
+                dy: 0,
+                mouseData: mouse_data as u32,
+                dwFlags: flag,
+                time: 0,
+                dwExtraInfo: 0,
+            },
+        },
+    };
+    unsafe {
+        SendInput(&[input], std::mem::size_of::<INPUT>() as i32);
+    }
+}
+
+#[cfg(not(windows))]
+fn send_mouse_button(_button: MouseButton, _down: bool) {}
+
+#[cfg(windows)]
+fn send_key(scancode: ScanCode, down: bool) {
+    let flags = if down {
+        KEYEVENTF_SCANCODE
+    } else {
+        KEYEVENTF_SCANCODE | KEYEVENTF_KEYUP
+    };
+    let input = INPUT {
+        r#type: INPUT_KEYBOARD,
+        Anonymous: INPUT_0 {
+            ki: KEYBDINPUT {
+                wVk: VIRTUAL_KEY(0),
+                wScan: scancode as u16,
+                dwFlags: flags,
+                time: 0,
+                dwExtraInfo: 0,
+            },
+        },
+    };
+    unsafe {
+        SendInput(&[input], std::mem::size_of::<INPUT>() as i32);
+    }
+}
+
+#[cfg(not(windows))]
+fn send_key(_scancode: ScanCode, _down: bool) {}
@@ -1,33 +1,49 @@
+mod aim_smoothing;
 mod capture;
 mod config;
+mod engine_cache;
 mod inference;
 mod input_listener;
+mod letterbox;
+mod macros;
 mod mouse_control;
+mod nms;
+mod overlay;
+mod remote_control;
 
-use crate::capture::ScreenCapture;
+use crate::capture::{capture_full, capture_region, create_capture_context, CaptureBackend};
 use crate::config::ConfigManager;
-use crate::inference::YoloInferencer;
+use crate::inference::InferenceEngine;
 use crate::input_listener::InputListener;
+use crate::overlay::{OverlayState, OverlayWindow};
+use crate::remote_control::{RemoteControlServer, RemoteHandles};
 use eframe::egui;
 use parking_lot::Mutex;
 use rfd::FileDialog;
+use serde::Serialize;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::thread;
 use std::time::{Duration, Instant};
 use tracing::{error, info};
 use tracing_subscriber::EnvFilter;
+use tracing_subscriber::prelude::*;
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
 enum LogLevel {
     Debug,
     Info,
     Error,
 }
 
+#[derive(Clone, Serialize)]
 struct LogEntry {
     level: LogLevel,
     message: String,
+    /// 与上一条日志完全相同（级别+内容）时的连续重复次数，避免同一条
+    /// 错误（例如每帧触发的推理失败）刷爆日志缓冲区。
+    count: u32,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -38,18 +54,48 @@ enum GuiRegionMode {
     Custom,
 }
 
+impl From<crate::config::RegionModeSetting> for GuiRegionMode {
+    fn from(mode: crate::config::RegionModeSetting) -> Self {
+        match mode {
+            crate::config::RegionModeSetting::Fullscreen => GuiRegionMode::Fullscreen,
+            crate::config::RegionModeSetting::Center640 => GuiRegionMode::Center640,
+            crate::config::RegionModeSetting::Center1280 => GuiRegionMode::Center1280,
+            crate::config::RegionModeSetting::Custom => GuiRegionMode::Custom,
+        }
+    }
+}
+
+impl From<GuiRegionMode> for crate::config::RegionModeSetting {
+    fn from(mode: GuiRegionMode) -> Self {
+        match mode {
+            GuiRegionMode::Fullscreen => crate::config::RegionModeSetting::Fullscreen,
+            GuiRegionMode::Center640 => crate::config::RegionModeSetting::Center640,
+            GuiRegionMode::Center1280 => crate::config::RegionModeSetting::Center1280,
+            GuiRegionMode::Custom => crate::config::RegionModeSetting::Custom,
+        }
+    }
+}
+
 #[derive(Clone, Copy)]
 struct CaptureConfig {
     mode: GuiRegionMode,
 }
 
+/// TensorRT 引擎缓存键中使用的网络输入分辨率；导出模型目前固定为方形
+/// 640x640，后续若支持可配置输入分辨率需同步更新这里。
+const ENGINE_INPUT_SIZE: u32 = 640;
+
 struct DetectionApp {
-    inferencer: Arc<YoloInferencer>,
+    inferencer: Arc<InferenceEngine>,
     input_listener: Arc<InputListener>,
     is_running: Arc<AtomicBool>,
     capture_config: Arc<Mutex<CaptureConfig>>,
     model_path: String,
     device_type: String,
+    /// 最近一次成功 `load_model` 所用的参数；远程控制收到 `set_device_type`
+    /// 时靠这份参数在新设备上重新加载模型，否则设备切换只是改了个标签，
+    /// 推理仍跑在原先加载时的设备上。
+    model_load_params: Arc<Mutex<Option<crate::remote_control::ModelLoadParams>>>,
     yolo_version: u8,
     gui_region_mode: GuiRegionMode,
     custom_x: u32,
@@ -61,11 +107,54 @@ struct DetectionApp {
     config_manager: Arc<Mutex<Option<ConfigManager>>>,
     control_yaw_sensitivity: f32,
     control_pitch_sensitivity: f32,
-    control_hotkey: String,
+    control_hotkey: crate::config::Hotkey,
     control_trigger_toggle: bool,
     control_x_target_offset: f32,
     control_y_target_offset: f32,
+    control_lead: f32,
+    control_alpha: f32,
+    control_max_step: f32,
+    control_input_backend: crate::config::InputBackendKind,
     capturing_hotkey: bool,
+    overlay_state: OverlayState,
+    overlay_window: Arc<Mutex<Option<OverlayWindow>>>,
+    overlay_enabled: bool,
+    selected_monitor_index: usize,
+    monitor_list: Vec<crate::capture::MonitorInfo>,
+    nms_iou_threshold: Arc<Mutex<f32>>,
+    objectness_threshold: Arc<Mutex<f32>>,
+    /// 当前生效的控制参数，与 `control_*` GUI 字段双向同步；检测线程与
+    /// 远程控制服务共享同一份句柄，修改立即生效，无需重启检测。
+    control_settings: Arc<Mutex<crate::config::ControlSettings>>,
+    /// 与 `device_type` 双向同步，供远程控制服务读写。
+    remote_device_type: Arc<Mutex<String>>,
+    remote_addr: String,
+    remote_server: Arc<Mutex<Option<RemoteControlServer>>>,
+    log_filter_debug: bool,
+    log_filter_info: bool,
+    log_filter_error: bool,
+    log_search: String,
+    /// TensorRT 引擎缓存键中的 FP16 标记；切换后重新选择模型加载时缓存键
+    /// 随之变化。该值不传给 `load_model`，不改变实际构建所用的精度。
+    fp16_enabled: bool,
+    /// 网络输入宽高，捕获画面会按此尺寸做 letterbox 缩放+填充后再推理。
+    input_width: u32,
+    input_height: u32,
+    /// 手动输入组合键字符串（如 "ctrl+shift+f13"）的文本框内容。
+    hotkey_text_input: String,
+    macro_recorder: Arc<crate::macros::MacroRecorder>,
+    /// 已保存的宏；开始检测时从中复制当前选中项交给检测线程，热键触发时
+    /// 回放的是那份副本。
+    macros: Arc<Mutex<Vec<crate::macros::Macro>>>,
+    macro_name_input: String,
+    selected_macro_index: Option<usize>,
+    /// 瞄准热键按下时是否顺带回放选中的宏（例如压枪位移序列）。
+    macro_bound_to_hotkey: bool,
+    macro_speed: f32,
+    macro_loop_count: u32,
+    /// 避免同一个宏播放未结束时又被重复触发，仅手动「播放」按钮与热键
+    /// 自动触发共用同一个守卫。
+    macro_playing: Arc<AtomicBool>,
 }
 
 impl DetectionApp {
@@ -84,26 +173,51 @@ impl DetectionApp {
             cc.egui_ctx.set_fonts(fonts);
         }
 
-        let config_manager = ConfigManager::new(std::path::PathBuf::from("config.json"));
+        // 落到 OS 级配置目录而非进程当前工作目录，避免从不同快捷方式/终端/
+        // 服务方式启动时各自读写互不相同的 config.json，导致设置“丢失”。
+        let config_path = dirs::config_dir()
+            .map(|dir| dir.join("luoluo-ai").join("config.json"))
+            .unwrap_or_else(|| std::path::PathBuf::from("config.json"));
+        let config_manager = ConfigManager::new(config_path);
         let config = config_manager.load();
+        let gui_region_mode: GuiRegionMode = config.capture.mode.clone().into();
+        let inferencer = Arc::new(InferenceEngine::new());
+        *inferencer.conf_threshold.lock() = config.inference.conf_threshold;
+
+        let logs = Arc::new(Mutex::new(Vec::new()));
+        push_log(
+            &logs,
+            LogLevel::Info,
+            format!(
+                "当前配置: 后端={} yolo={} 置信度={:.2} objectness={:.2} nms_iou={:.2} 区域模式={:?} 显示器#{}",
+                config.inference.device_type,
+                config.inference.yolo_version,
+                config.inference.conf_threshold,
+                config.inference.objectness_threshold,
+                config.inference.nms_iou_threshold,
+                config.capture.mode,
+                config.capture.monitor_index,
+            ),
+        );
 
         Self {
-            inferencer: Arc::new(YoloInferencer::new()),
+            inferencer,
             input_listener: Arc::new(InputListener::new()),
             is_running: Arc::new(AtomicBool::new(false)),
             capture_config: Arc::new(Mutex::new(CaptureConfig {
-                mode: GuiRegionMode::Fullscreen,
+                mode: gui_region_mode,
             })),
             model_path: "未选择".to_string(),
-            device_type: "cpu".to_string(),
-            yolo_version: 26,
-            gui_region_mode: GuiRegionMode::Fullscreen,
-            custom_x: 0,
-            custom_y: 0,
-            custom_width: 1280,
-            custom_height: 720,
+            device_type: config.inference.device_type.clone(),
+            model_load_params: Arc::new(Mutex::new(None)),
+            yolo_version: config.inference.yolo_version,
+            gui_region_mode,
+            custom_x: config.capture.custom_x,
+            custom_y: config.capture.custom_y,
+            custom_width: config.capture.custom_width,
+            custom_height: config.capture.custom_height,
             selected_tab: 0,
-            logs: Arc::new(Mutex::new(Vec::new())),
+            logs,
             config_manager: Arc::new(Mutex::new(Some(config_manager))),
             control_yaw_sensitivity: config.control.yaw_sensitivity,
             control_pitch_sensitivity: config.control.pitch_sensitivity,
@@ -112,7 +226,79 @@ impl DetectionApp {
                 == crate::config::TriggerType::Toggle,
             control_x_target_offset: config.control.x_target_offset,
             control_y_target_offset: config.control.y_target_offset,
+            control_lead: config.control.lead,
+            control_alpha: config.control.alpha,
+            control_max_step: config.control.max_step,
+            control_input_backend: config.control.input_backend,
             capturing_hotkey: false,
+            overlay_state: OverlayState::new(),
+            overlay_window: Arc::new(Mutex::new(None)),
+            overlay_enabled: false,
+            selected_monitor_index: config.capture.monitor_index,
+            monitor_list: crate::capture::enumerate_monitors().unwrap_or_default(),
+            nms_iou_threshold: Arc::new(Mutex::new(config.inference.nms_iou_threshold)),
+            objectness_threshold: Arc::new(Mutex::new(config.inference.objectness_threshold)),
+            control_settings: Arc::new(Mutex::new(config.control.clone())),
+            remote_device_type: Arc::new(Mutex::new(config.inference.device_type.clone())),
+            remote_addr: "127.0.0.1:7878".to_string(),
+            remote_server: Arc::new(Mutex::new(None)),
+            log_filter_debug: cfg!(debug_assertions),
+            log_filter_info: true,
+            log_filter_error: true,
+            log_search: String::new(),
+            fp16_enabled: config.inference.fp16,
+            input_width: config.inference.input_width,
+            input_height: config.inference.input_height,
+            hotkey_text_input: String::new(),
+            macro_recorder: Arc::new(crate::macros::MacroRecorder::new()),
+            macros: Arc::new(Mutex::new(config.macros)),
+            macro_name_input: String::new(),
+            selected_macro_index: None,
+            macro_bound_to_hotkey: false,
+            macro_speed: 1.0,
+            macro_loop_count: 1,
+            macro_playing: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// 把当前内存中的设置打包成一份 `AppConfig`，用于显式保存按钮与退出时兜底持久化。
+    fn snapshot_config(&self) -> crate::config::AppConfig {
+        crate::config::AppConfig {
+            control: crate::config::ControlSettings {
+                yaw_sensitivity: self.control_yaw_sensitivity,
+                pitch_sensitivity: self.control_pitch_sensitivity,
+                hotkey: self.control_hotkey.clone(),
+                trigger_type: if self.control_trigger_toggle {
+                    crate::config::TriggerType::Toggle
+                } else {
+                    crate::config::TriggerType::Hold
+                },
+                x_target_offset: self.control_x_target_offset,
+                y_target_offset: self.control_y_target_offset,
+                lead: self.control_lead,
+                alpha: self.control_alpha,
+                max_step: self.control_max_step,
+                input_backend: self.control_input_backend,
+            },
+            inference: crate::config::InferenceSettings {
+                device_type: self.device_type.clone(),
+                yolo_version: self.yolo_version,
+                conf_threshold: *self.inferencer.conf_threshold.lock(),
+                nms_iou_threshold: *self.nms_iou_threshold.lock(),
+                objectness_threshold: *self.objectness_threshold.lock(),
+                fp16: self.fp16_enabled,
+                input_width: self.input_width,
+                input_height: self.input_height,
+            },
+            capture: crate::config::CaptureSettings {
+                mode: self.gui_region_mode.into(),
+                custom_x: self.custom_x,
+                custom_y: self.custom_y,
+                custom_width: self.custom_width,
+                custom_height: self.custom_height,
+                monitor_index: self.selected_monitor_index,
+            },
+            macros: self.macros.lock().clone(),
         }
     }
 }
@@ -133,14 +319,87 @@ impl eframe::App for DetectionApp {
                         return;
                     };
                     self.model_path = path.display().to_string();
-                    let device = self.device_type.clone();
                     let yolo_version = self.yolo_version;
 
-                    match self
-                        .inferencer
-                        .load_model(&path, &device, yolo_version)
-                    {
+                    let mut engine_cache_entry = None;
+                    if self.device_type == "tensorrt" {
+                        match crate::engine_cache::hash_model_file(&path) {
+                            Ok(model_hash) => {
+                                let key = crate::engine_cache::EngineCacheKey {
+                                    model_hash,
+                                    yolo_version,
+                                    fp16: self.fp16_enabled,
+                                    input_width: self.input_width,
+                                    input_height: self.input_height,
+                                };
+                                let cache = crate::engine_cache::EngineCache::new("engine_cache");
+                                if cache.contains(&key) {
+                                    push_log(
+                                        &self.logs,
+                                        LogLevel::Info,
+                                        "发现匹配的 TensorRT 引擎缓存记录，本次加载应可直接复用",
+                                    );
+                                } else {
+                                    push_log(
+                                        &self.logs,
+                                        LogLevel::Info,
+                                        "未找到匹配的 TensorRT 引擎缓存，本次构建可能需要数分钟",
+                                    );
+                                }
+                                engine_cache_entry = Some((cache, key));
+                            }
+                            Err(e) => {
+                                push_log(
+                                    &self.logs,
+                                    LogLevel::Error,
+                                    format!("计算模型哈希失败，跳过引擎缓存: {}", e),
+                                );
+                            }
+                        }
+                    }
+
+                    let mut device = self.device_type.clone();
+                    let mut result = self.inferencer.load_model(
+                        path.clone(),
+                        &device,
+                        Some(yolo_version),
+                        self.input_width,
+                        self.input_height,
+                    );
+                    if let Err(ref e) = result {
+                        if device == "tensorrt" {
+                            push_log(
+                                &self.logs,
+                                LogLevel::Error,
+                                format!("TensorRT 引擎构建失败，回退到 CUDA: {:#}", e),
+                            );
+                            device = "cuda".to_string();
+                            self.device_type = device.clone();
+                            engine_cache_entry = None;
+                            result = self.inferencer.load_model(
+                                path.clone(),
+                                &device,
+                                Some(yolo_version),
+                                self.input_width,
+                                self.input_height,
+                            );
+                        }
+                    }
+
+                    if result.is_ok() {
+                        *self.model_load_params.lock() = Some(crate::remote_control::ModelLoadParams {
+                            path: path.clone(),
+                            yolo_version,
+                            input_width: self.input_width,
+                            input_height: self.input_height,
+                        });
+                    }
+
+                    match result {
                         Ok(_) => {
+                            if let Some((cache, key)) = engine_cache_entry {
+                                cache.record(&key);
+                            }
                             info!("模型加载成功");
                             push_log(&self.logs, LogLevel::Info, "模型加载成功");
                         }
@@ -169,30 +428,34 @@ impl eframe::App for DetectionApp {
                     let is_running = self.is_running.clone();
                     let capture_config = self.capture_config.clone();
                     let logs = self.logs.clone();
-                    let trigger_toggle = self.control_trigger_toggle;
+                    let overlay_state = self.overlay_state.clone();
+                    let monitor_index = self.selected_monitor_index;
+                    let nms_iou_threshold = self.nms_iou_threshold.clone();
+                    let objectness_threshold = self.objectness_threshold.clone();
                     let custom_x = self.custom_x;
                     let custom_y = self.custom_y;
                     let custom_width = self.custom_width;
                     let custom_height = self.custom_height;
-                    let control_config = crate::config::ControlSettings {
-                        yaw_sensitivity: self.control_yaw_sensitivity,
-                        pitch_sensitivity: self.control_pitch_sensitivity,
-                        hotkey: self.control_hotkey.clone(),
-                        trigger_type: if trigger_toggle {
-                            crate::config::TriggerType::Toggle
-                        } else {
-                            crate::config::TriggerType::Hold
-                        },
-                        x_target_offset: self.control_x_target_offset,
-                        y_target_offset: self.control_y_target_offset,
-                    };
+                    let control_settings = self.control_settings.clone();
+                    let net_input_width = self.input_width;
+                    let net_input_height = self.input_height;
                     let input_listener = self.input_listener.clone();
-                    input_listener.set_hotkey(&control_config.hotkey);
+                    input_listener.set_hotkey(control_settings.lock().hotkey.clone());
                     input_listener.set_toggle_state(false);
-                    input_listener.start();
-                    
+                    input_listener.start(control_settings.lock().input_backend);
+                    let macro_recorder = self.macro_recorder.clone();
+                    let macro_playing = self.macro_playing.clone();
+                    let macro_speed = self.macro_speed;
+                    let macro_loop_count = self.macro_loop_count;
+                    let selected_macro_for_hotkey = if self.macro_bound_to_hotkey {
+                        self.selected_macro_index
+                            .and_then(|index| self.macros.lock().get(index).cloned())
+                    } else {
+                        None
+                    };
+
                     thread::spawn(move || {
-                        let mut capture = match ScreenCapture::try_new(0) {
+                        let mut capture = match create_capture_context(monitor_index, 0) {
                             Ok(c) => c,
                             Err(e) => {
                                 push_log(&logs, LogLevel::Error, format!("捕获初始化失败: {}", e));
@@ -208,7 +471,9 @@ impl eframe::App for DetectionApp {
                         
                         let screen_center_x = capture.width() as f32 / 2.0;
                         let screen_center_y = capture.height() as f32 / 2.0;
-                        
+                        let mut target_tracker = crate::aim_smoothing::TargetTracker::new();
+                        let mut had_target_last_frame = false;
+
                         loop {
                             if !is_running.load(Ordering::SeqCst) {
                                 break;
@@ -219,11 +484,11 @@ impl eframe::App for DetectionApp {
                             let full_w = capture.width();
                             let full_h = capture.height();
                             
-                            let (did_capture, width, height) = match config.mode {
+                            let (did_capture, width, height, offset_x, offset_y) = match config.mode {
                                 GuiRegionMode::Fullscreen => {
-                                    let did = capture.capture_full().map_err(|e| e.to_string());
+                                    let did = capture_full(&mut capture).map_err(|e| e.to_string());
                                     match did {
-                                        Ok(d) => (d, full_w, full_h),
+                                        Ok(d) => (d, full_w, full_h, 0, 0),
                                         Err(e) => {
                                             push_log(&logs, LogLevel::Error, format!("捕获失败: {}", e));
                                             is_running.store(false, Ordering::SeqCst);
@@ -236,8 +501,8 @@ impl eframe::App for DetectionApp {
                                     let h = 640.min(full_h);
                                     let x = (full_w - w) / 2;
                                     let y = (full_h - h) / 2;
-                                    match capture.capture_region(x, y, w, h) {
-                                        Ok(d) => (d, w, h),
+                                    match capture_region(&mut capture, x, y, w, h) {
+                                        Ok(d) => (d, w, h, x, y),
                                         Err(e) => {
                                             push_log(&logs, LogLevel::Error, format!("捕获失败: {}", e));
                                             is_running.store(false, Ordering::SeqCst);
@@ -250,8 +515,8 @@ impl eframe::App for DetectionApp {
                                     let h = 1280.min(full_h);
                                     let x = (full_w - w) / 2;
                                     let y = (full_h - h) / 2;
-                                    match capture.capture_region(x, y, w, h) {
-                                        Ok(d) => (d, w, h),
+                                    match capture_region(&mut capture, x, y, w, h) {
+                                        Ok(d) => (d, w, h, x, y),
                                         Err(e) => {
                                             push_log(&logs, LogLevel::Error, format!("捕获失败: {}", e));
                                             is_running.store(false, Ordering::SeqCst);
@@ -264,8 +529,8 @@ impl eframe::App for DetectionApp {
                                     if width > 0 && height > 0 && x < full_w && y < full_h {
                                         let w = width.min(full_w - x);
                                         let h = height.min(full_h - y);
-                                        match capture.capture_region(x, y, w, h) {
-                                            Ok(d) => (d, w, h),
+                                        match capture_region(&mut capture, x, y, w, h) {
+                                            Ok(d) => (d, w, h, x, y),
                                             Err(e) => {
                                                 push_log(&logs, LogLevel::Error, format!("捕获失败: {}", e));
                                                 is_running.store(false, Ordering::SeqCst);
@@ -273,8 +538,8 @@ impl eframe::App for DetectionApp {
                                             }
                                         }
                                     } else {
-                                        match capture.capture_full() {
-                                            Ok(d) => (d, full_w, full_h),
+                                        match capture_full(&mut capture) {
+                                            Ok(d) => (d, full_w, full_h, 0, 0),
                                             Err(e) => {
                                                 push_log(&logs, LogLevel::Error, format!("捕获失败: {}", e));
                                                 is_running.store(false, Ordering::SeqCst);
@@ -284,7 +549,7 @@ impl eframe::App for DetectionApp {
                                     }
                                 }
                             };
-                            
+
                             let capture_ms = capture_start.elapsed().as_secs_f64() * 1000.0;
                             
                             if !did_capture || capture.rgba_buffer.is_empty() {
@@ -292,8 +557,23 @@ impl eframe::App for DetectionApp {
                             }
                             
                             let infer_start = Instant::now();
-                            let infer_result = inferencer.infer_with_preprocess(&capture.rgba_buffer, width, height);
+                            // 网络输入尺寸在加载模型时已经通过 Config::with_model_ixx
+                            // 写死（见 InferenceEngine::load_model），usls 在 model.run
+                            // 内部据此对输入画面做 letterbox 缩放，这里不需要重复缩放；
+                            // 下面的 `letterbox_transform.unmap_box` 用同一组 net_input_*
+                            // 尺寸把检测框坐标映射回捕获画面空间。
+                            let infer_result = inferencer.infer_with_preprocess(
+                                &capture.rgba_buffer,
+                                width,
+                                height,
+                            );
                             let infer_ms = infer_start.elapsed().as_secs_f64() * 1000.0;
+                            let letterbox_transform = crate::letterbox::compute_transform(
+                                width,
+                                height,
+                                net_input_width,
+                                net_input_height,
+                            );
                             
                             match infer_result {
                                 Ok(results) => {
@@ -301,49 +581,131 @@ impl eframe::App for DetectionApp {
                                     capture_total += capture_ms;
                                     infer_total += infer_ms;
                                     
-                                    if input_listener.is_hotkey_pressed() && input_listener.check_trigger_cooldown(100)
-                                        && let Some(y) = results.first()
-                                    {
-                                        let boxes = y.hbbs();
-                                        if !boxes.is_empty() {
-                                                let mut closest_box: Option<[f32; 4]> = None;
-                                                let mut min_dist = f32::MAX;
-                                                
-                                                for det in boxes {
-                                                    let x1 = det.xmin();
-                                                    let y1 = det.ymin();
-                                                    let x2 = det.xmax();
-                                                    let y2 = det.ymax();
-                                                    let center_x = (x1 + x2) / 2.0;
-                                                    let center_y = (y1 + y2) / 2.0;
-                                                    let dist = ((center_x - screen_center_x).powi(2) + (center_y - screen_center_y).powi(2)).sqrt();
-                                                    if dist < min_dist {
-                                                        min_dist = dist;
-                                                        closest_box = Some([x1, y1, x2, y2]);
-                                                    }
+                                    if let Some(y) = results.first() {
+                                        let control_config = control_settings.lock().clone();
+                                        input_listener.set_hotkey(control_config.hotkey.clone());
+                                        let raw_boxes = y.hbbs();
+                                        let objectness = *objectness_threshold.lock();
+                                        let conf_threshold = *inferencer.conf_threshold.lock();
+                                        let iou_threshold = *nms_iou_threshold.lock();
+                                        // usls 对 YOLO 的解码已经把 objectness 与 class_conf 相乘，
+                                        // `confidence()` 返回的就是 score = objectness * class_conf；
+                                        // 这里先用 objectness_threshold 做一次粗筛，再用 conf_threshold
+                                        // 做请求中规定的最终阈值，两者作用于同一个组合分数。
+                                        let candidates: Vec<crate::nms::Candidate> = raw_boxes
+                                            .iter()
+                                            .filter(|det| {
+                                                det.confidence() >= objectness
+                                                    && det.confidence() >= conf_threshold
+                                            })
+                                            .map(|det| crate::nms::Candidate {
+                                                bbox: crate::nms::clamp_to_region(
+                                                    letterbox_transform.unmap_box([
+                                                        det.xmin(),
+                                                        det.ymin(),
+                                                        det.xmax(),
+                                                        det.ymax(),
+                                                    ]),
+                                                    width as f32,
+                                                    height as f32,
+                                                ),
+                                                score: det.confidence(),
+                                                class_id: det.id().unwrap_or(0),
+                                            })
+                                            .collect();
+                                        let boxes = crate::nms::greedy_nms(&candidates, iou_threshold);
+
+                                        if overlay_state.enabled.load(Ordering::SeqCst) {
+                                            let absolute: Vec<[f32; 4]> = boxes
+                                                .iter()
+                                                .map(|det| {
+                                                    [
+                                                        det.bbox[0] + offset_x as f32,
+                                                        det.bbox[1] + offset_y as f32,
+                                                        det.bbox[2] + offset_x as f32,
+                                                        det.bbox[3] + offset_y as f32,
+                                                    ]
+                                                })
+                                                .collect();
+                                            *overlay_state.boxes.lock() = absolute;
+                                        }
+
+                                        if input_listener.is_hotkey_pressed() && !boxes.is_empty() {
+                                            let mut closest_box: Option<[f32; 4]> = None;
+                                            let mut min_dist = f32::MAX;
+
+                                            for det in &boxes {
+                                                let [x1, y1, x2, y2] = det.bbox;
+                                                let center_x = (x1 + x2) / 2.0;
+                                                let center_y = (y1 + y2) / 2.0;
+                                                let dist = ((center_x - screen_center_x).powi(2) + (center_y - screen_center_y).powi(2)).sqrt();
+                                                if dist < min_dist {
+                                                    min_dist = dist;
+                                                    closest_box = Some(det.bbox);
                                                 }
-                                                
-                                                if let Some(box_coords) = closest_box {
-                                                    let x1 = box_coords[0];
-                                                    let y1 = box_coords[1];
-                                                    let x2 = box_coords[2];
-                                                    let y2 = box_coords[3];
-                                                    
-                                                    let target_x = x1 + (x2 - x1) * (0.5 + control_config.x_target_offset);
-                                                    let target_y = y1 + (y2 - y1) * (0.5 + control_config.y_target_offset);
-                                                    
-                                                    let dx = target_x - screen_center_x;
-                                                    let dy = target_y - screen_center_y;
-                                                    
-                                                    let move_x = dx * control_config.yaw_sensitivity;
-                                                    let move_y = dy * control_config.pitch_sensitivity;
-                                                    
-                                                    crate::mouse_control::move_relative(move_x as i32, move_y as i32);
-                                                    
-                                                    push_log(&logs, LogLevel::Debug, format!("移动: ({:.1}, {:.1}) -> ({:.1}, {:.1})", dx, dy, move_x, move_y));
+                                            }
+
+                                            if let Some(box_coords) = closest_box {
+                                                let x1 = box_coords[0];
+                                                let y1 = box_coords[1];
+                                                let x2 = box_coords[2];
+                                                let y2 = box_coords[3];
+
+                                                if !had_target_last_frame {
+                                                    target_tracker.reset();
                                                 }
+                                                had_target_last_frame = true;
+                                                let center = ((x1 + x2) / 2.0, (y1 + y2) / 2.0);
+                                                let velocity = target_tracker.update(center);
+
+                                                let target_x = x1 + (x2 - x1) * (0.5 + control_config.x_target_offset)
+                                                    + velocity.0 * control_config.lead;
+                                                let target_y = y1 + (y2 - y1) * (0.5 + control_config.y_target_offset)
+                                                    + velocity.1 * control_config.lead;
+
+                                                let dx = target_x - screen_center_x;
+                                                let dy = target_y - screen_center_y;
+                                                let (step_dx, step_dy) = crate::aim_smoothing::ease_step(
+                                                    dx,
+                                                    dy,
+                                                    control_config.alpha,
+                                                    control_config.max_step,
+                                                );
+
+                                                let move_x = step_dx * control_config.yaw_sensitivity;
+                                                let move_y = step_dy * control_config.pitch_sensitivity;
+
+                                                crate::mouse_control::move_relative(move_x as i32, move_y as i32);
+
+                                                push_log(&logs, LogLevel::Debug, format!("移动: ({:.1}, {:.1}) -> ({:.1}, {:.1})", dx, dy, move_x, move_y));
+                                            } else {
+                                                had_target_last_frame = false;
                                             }
+                                        } else {
+                                            had_target_last_frame = false;
+                                        }
+                                    }
+
+                                    if let Some(ref bound_macro) = selected_macro_for_hotkey {
+                                        if input_listener.is_hotkey_pressed()
+                                            && !macro_playing.load(Ordering::SeqCst)
+                                            && macro_recorder.check_play_cooldown(500)
+                                        {
+                                            macro_playing.store(true, Ordering::SeqCst);
+                                            let macro_to_play = bound_macro.clone();
+                                            let is_running_guard = is_running.clone();
+                                            let macro_playing_guard = macro_playing.clone();
+                                            thread::spawn(move || {
+                                                crate::macros::play(
+                                                    &macro_to_play,
+                                                    macro_speed,
+                                                    macro_loop_count,
+                                                    &is_running_guard,
+                                                );
+                                                macro_playing_guard.store(false, Ordering::SeqCst);
+                                            });
                                         }
+                                    }
                                 }
                                 Err(e) => {
                                     push_log(&logs, LogLevel::Error, format!("推理失败: {}", e));
@@ -402,7 +764,7 @@ impl eframe::App for DetectionApp {
                                 LogLevel::Info => "[INFO] ",
                                 LogLevel::Error => "[ERROR] ",
                             };
-                            ui.label(format!("{}{}", prefix, entry.message));
+                            ui.label(format!("{}{}", prefix, format_log_entry(entry)));
                         }
                     });
             });
@@ -415,9 +777,26 @@ impl eframe::App for DetectionApp {
                 ui.selectable_value(&mut self.selected_tab, 0, "控制设置");
                 ui.selectable_value(&mut self.selected_tab, 1, "推理配置");
                 ui.selectable_value(&mut self.selected_tab, 2, "捕获设置");
+                ui.selectable_value(&mut self.selected_tab, 3, "远程控制");
+                ui.selectable_value(&mut self.selected_tab, 4, "日志");
+                ui.selectable_value(&mut self.selected_tab, 5, "宏");
             });
             ui.add_space(10.0);
             if self.selected_tab == 0 {
+                if !self.capturing_hotkey {
+                    let shared = self.control_settings.lock().clone();
+                    self.control_yaw_sensitivity = shared.yaw_sensitivity;
+                    self.control_pitch_sensitivity = shared.pitch_sensitivity;
+                    self.control_hotkey = shared.hotkey;
+                    self.control_trigger_toggle = shared.trigger_type == crate::config::TriggerType::Toggle;
+                    self.control_x_target_offset = shared.x_target_offset;
+                    self.control_y_target_offset = shared.y_target_offset;
+                    self.control_lead = shared.lead;
+                    self.control_alpha = shared.alpha;
+                    self.control_max_step = shared.max_step;
+                    self.control_input_backend = shared.input_backend;
+                }
+
                 ui.horizontal(|ui| {
                     ui.label("Yaw灵敏度:");
                     ui.add(egui::Slider::new(
@@ -451,7 +830,10 @@ impl eframe::App for DetectionApp {
                             self.capturing_hotkey = false;
                         }
                     } else if ui
-                        .button(format!("设置: {}", self.control_hotkey))
+                        .button(format!(
+                            "设置: {}",
+                            crate::input_listener::InputListener::describe_hotkey(&self.control_hotkey)
+                        ))
                         .clicked()
                     {
                         self.capturing_hotkey = true;
@@ -459,6 +841,42 @@ impl eframe::App for DetectionApp {
                     }
                 });
 
+                ui.horizontal(|ui| {
+                    ui.label("或手动输入组合键 (如 ctrl+shift+f13):");
+                    ui.text_edit_singleline(&mut self.hotkey_text_input);
+                    if ui.button("应用").clicked() {
+                        match crate::input_listener::InputListener::parse_accelerator(
+                            &self.hotkey_text_input,
+                        ) {
+                            Ok(hotkey) => {
+                                self.control_hotkey = hotkey;
+                                push_log(
+                                    &self.logs,
+                                    LogLevel::Info,
+                                    format!("热键已设置为: {}", self.hotkey_text_input),
+                                );
+                            }
+                            Err(e) => {
+                                push_log(&self.logs, LogLevel::Error, format!("组合键解析失败: {}", e));
+                            }
+                        }
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("热键监听后端:");
+                    ui.selectable_value(
+                        &mut self.control_input_backend,
+                        crate::config::InputBackendKind::Polling,
+                        "固定周期轮询",
+                    );
+                    ui.selectable_value(
+                        &mut self.control_input_backend,
+                        crate::config::InputBackendKind::LowLevelHook,
+                        "低级钩子(事件驱动)",
+                    );
+                });
+
                 ui.add_space(10.0);
 
                 ui.horizontal(|ui| {
@@ -479,6 +897,23 @@ impl eframe::App for DetectionApp {
                     ui.label(format!("{:.2}", self.control_y_target_offset));
                 });
 
+                ui.add_space(10.0);
+
+                ui.horizontal(|ui| {
+                    ui.label("移动预判(lead):");
+                    ui.add(egui::Slider::new(&mut self.control_lead, 0.0..=0.5).step_by(0.01));
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("缓出系数(alpha):");
+                    ui.add(egui::Slider::new(&mut self.control_alpha, 0.1..=1.0).step_by(0.01));
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("单帧最大位移(px):");
+                    ui.add(egui::Slider::new(&mut self.control_max_step, 1.0..=500.0));
+                });
+
                 ui.add_space(15.0);
 
                 if ui.button("保存控制设置").clicked()
@@ -489,7 +924,7 @@ impl eframe::App for DetectionApp {
                     } else {
                         crate::config::TriggerType::Hold
                     };
-                    let captured = self.input_listener.get_captured_key();
+                    let captured = self.input_listener.get_captured_hotkey();
                     let hotkey = if let Some(key) = captured {
                         self.input_listener.stop_capture();
                         key
@@ -504,10 +939,33 @@ impl eframe::App for DetectionApp {
                         trigger_type,
                         x_target_offset: self.control_x_target_offset,
                         y_target_offset: self.control_y_target_offset,
+                        lead: self.control_lead,
+                        alpha: self.control_alpha,
+                        max_step: self.control_max_step,
+                        input_backend: self.control_input_backend,
                     });
                     push_log(&self.logs, LogLevel::Info, "控制设置已保存");
                 }
+
+                *self.control_settings.lock() = crate::config::ControlSettings {
+                    yaw_sensitivity: self.control_yaw_sensitivity,
+                    pitch_sensitivity: self.control_pitch_sensitivity,
+                    hotkey: self.control_hotkey.clone(),
+                    trigger_type: if self.control_trigger_toggle {
+                        crate::config::TriggerType::Toggle
+                    } else {
+                        crate::config::TriggerType::Hold
+                    },
+                    x_target_offset: self.control_x_target_offset,
+                    y_target_offset: self.control_y_target_offset,
+                    lead: self.control_lead,
+                    alpha: self.control_alpha,
+                    max_step: self.control_max_step,
+                    input_backend: self.control_input_backend,
+                };
             } else if self.selected_tab == 1 {
+                self.device_type = self.remote_device_type.lock().clone();
+
                 ui.horizontal(|ui| {
                     ui.label("推理后端:");
                     ui.selectable_value(&mut self.device_type, "cpu".to_string(), "CPU");
@@ -520,6 +978,22 @@ impl eframe::App for DetectionApp {
                     ui.label("(开启 CUDA 后建议使用 TensorRT 或 FP16 模型)");
                 });
 
+                ui.add_enabled_ui(self.device_type == "tensorrt", |ui| {
+                    ui.checkbox(&mut self.fp16_enabled, "TensorRT 引擎使用 FP16 精度");
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("网络输入宽:");
+                    ui.add(egui::DragValue::new(&mut self.input_width).speed(1));
+                    ui.label("高:");
+                    ui.add(egui::DragValue::new(&mut self.input_height).speed(1));
+                    if ui.button("恢复默认").clicked() {
+                        let (w, h) = crate::config::default_input_size(self.yolo_version);
+                        self.input_width = w;
+                        self.input_height = h;
+                    }
+                });
+
                 ui.horizontal(|ui| {
                     ui.label("YOLO版本:");
                     ui.selectable_value(&mut self.yolo_version, 26, "26");
@@ -541,7 +1015,66 @@ impl eframe::App for DetectionApp {
                         *self.inferencer.conf_threshold.lock() = conf;
                     }
                 });
+
+                ui.horizontal(|ui| {
+                    ui.label("Objectness阈值:");
+                    let mut objectness = *self.objectness_threshold.lock();
+                    if ui.add(egui::Slider::new(&mut objectness, 0.0..=1.0)).changed() {
+                        *self.objectness_threshold.lock() = objectness;
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("NMS IoU阈值:");
+                    let mut nms_iou = *self.nms_iou_threshold.lock();
+                    if ui.add(egui::Slider::new(&mut nms_iou, 0.0..=1.0)).changed() {
+                        *self.nms_iou_threshold.lock() = nms_iou;
+                    }
+                });
+
+                ui.add_space(15.0);
+
+                if ui.button("保存推理设置").clicked()
+                    && let Some(ref manager) = *self.config_manager.lock()
+                {
+                    manager.update_inference(crate::config::InferenceSettings {
+                        device_type: self.device_type.clone(),
+                        yolo_version: self.yolo_version,
+                        conf_threshold: *self.inferencer.conf_threshold.lock(),
+                        nms_iou_threshold: *self.nms_iou_threshold.lock(),
+                        objectness_threshold: *self.objectness_threshold.lock(),
+                        fp16: self.fp16_enabled,
+                        input_width: self.input_width,
+                        input_height: self.input_height,
+                    });
+                    push_log(&self.logs, LogLevel::Info, "推理设置已保存");
+                }
+
+                *self.remote_device_type.lock() = self.device_type.clone();
             } else if self.selected_tab == 2 {
+                ui.horizontal(|ui| {
+                    ui.label("捕获显示器:");
+                    let current_name = self
+                        .monitor_list
+                        .get(self.selected_monitor_index)
+                        .map(|m| format!("{} ({}x{})", m.name, m.width, m.height))
+                        .unwrap_or_else(|| "主显示器".to_string());
+                    egui::ComboBox::new("monitor_select", "")
+                        .selected_text(current_name)
+                        .show_ui(ui, |ui| {
+                            for monitor in &self.monitor_list {
+                                ui.selectable_value(
+                                    &mut self.selected_monitor_index,
+                                    monitor.index,
+                                    format!("{} ({}x{})", monitor.name, monitor.width, monitor.height),
+                                );
+                            }
+                        });
+                    if ui.button("刷新").clicked() {
+                        self.monitor_list = crate::capture::enumerate_monitors().unwrap_or_default();
+                    }
+                });
+
                 ui.horizontal(|ui| {
                     ui.label("区域设置:");
                     ui.radio_value(
@@ -573,26 +1106,367 @@ impl eframe::App for DetectionApp {
                     });
                 }
 
-                let mut config = self.capture_config.lock();
-                config.mode = self.gui_region_mode;
+                {
+                    let mut config = self.capture_config.lock();
+                    config.mode = self.gui_region_mode;
+                }
+
+                ui.add_space(10.0);
+
+                if ui
+                    .checkbox(&mut self.overlay_enabled, "显示检测框覆盖层（穿透点击）")
+                    .changed()
+                {
+                    if self.overlay_enabled {
+                        let mut window = self.overlay_window.lock();
+                        if window.is_none() {
+                            let selected = self.monitor_list.get(self.selected_monitor_index);
+                            let geometry = match selected {
+                                Some(monitor) => {
+                                    Ok((monitor.x, monitor.y, monitor.width, monitor.height))
+                                }
+                                None => crate::capture::primary_monitor_size()
+                                    .map(|(w, h)| (0, 0, w, h)),
+                            };
+                            match geometry {
+                                Ok((x, y, w, h)) => {
+                                    *window = Some(OverlayWindow::start(
+                                        self.overlay_state.clone(),
+                                        x,
+                                        y,
+                                        w,
+                                        h,
+                                    ));
+                                    self.overlay_state.enabled.store(true, Ordering::SeqCst);
+                                    push_log(&self.logs, LogLevel::Info, "覆盖层已开启");
+                                }
+                                Err(e) => {
+                                    self.overlay_enabled = false;
+                                    push_log(
+                                        &self.logs,
+                                        LogLevel::Error,
+                                        format!("覆盖层开启失败: {}", e),
+                                    );
+                                }
+                            }
+                        } else {
+                            self.overlay_state.enabled.store(true, Ordering::SeqCst);
+                        }
+                    } else {
+                        self.overlay_state.enabled.store(false, Ordering::SeqCst);
+                        push_log(&self.logs, LogLevel::Info, "覆盖层已关闭");
+                    }
+                }
+
+                ui.add_space(15.0);
+
+                if ui.button("保存捕获设置").clicked()
+                    && let Some(ref manager) = *self.config_manager.lock()
+                {
+                    manager.update_capture(crate::config::CaptureSettings {
+                        mode: self.gui_region_mode.into(),
+                        custom_x: self.custom_x,
+                        custom_y: self.custom_y,
+                        custom_width: self.custom_width,
+                        custom_height: self.custom_height,
+                        monitor_index: self.selected_monitor_index,
+                    });
+                    push_log(&self.logs, LogLevel::Info, "捕获设置已保存");
+                }
+            } else if self.selected_tab == 3 {
+                ui.label("允许无头/第二台机器通过 TCP 实时下发控制参数，并接收同一份日志流。");
+                ui.add_space(10.0);
+
+                let running = self.remote_server.lock().is_some();
+                ui.horizontal(|ui| {
+                    ui.label("监听地址:");
+                    ui.add_enabled(
+                        !running,
+                        egui::TextEdit::singleline(&mut self.remote_addr),
+                    );
+                });
+
+                ui.add_space(10.0);
+
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(!running, egui::Button::new("启动远程控制服务"))
+                        .clicked()
+                    {
+                        let handles = RemoteHandles {
+                            control_settings: self.control_settings.clone(),
+                            conf_threshold: self.inferencer.conf_threshold.clone(),
+                            capture_config: self.capture_config.clone(),
+                            device_type: self.remote_device_type.clone(),
+                            logs: self.logs.clone(),
+                            inferencer: self.inferencer.clone(),
+                            model_load_params: self.model_load_params.clone(),
+                        };
+                        match RemoteControlServer::start(&self.remote_addr, handles) {
+                            Ok(server) => {
+                                *self.remote_server.lock() = Some(server);
+                                push_log(
+                                    &self.logs,
+                                    LogLevel::Info,
+                                    format!("远程控制服务已启动: {}", self.remote_addr),
+                                );
+                            }
+                            Err(e) => {
+                                push_log(
+                                    &self.logs,
+                                    LogLevel::Error,
+                                    format!("远程控制服务启动失败: {}", e),
+                                );
+                            }
+                        }
+                    }
+
+                    if ui
+                        .add_enabled(running, egui::Button::new("停止远程控制服务"))
+                        .clicked()
+                    {
+                        if let Some(server) = self.remote_server.lock().take() {
+                            server.stop();
+                        }
+                        push_log(&self.logs, LogLevel::Info, "远程控制服务已停止");
+                    }
+                });
+            } else if self.selected_tab == 4 {
+                ui.horizontal(|ui| {
+                    ui.label("级别:");
+                    ui.checkbox(&mut self.log_filter_debug, "Debug");
+                    ui.checkbox(&mut self.log_filter_info, "Info");
+                    ui.checkbox(&mut self.log_filter_error, "Error");
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("搜索:");
+                    ui.text_edit_singleline(&mut self.log_search);
+                });
+
+                ui.add_space(10.0);
+
+                if ui.button("导出日志到文件").clicked() {
+                    if let Some(path) = FileDialog::new()
+                        .set_file_name("luoluo-ai.log")
+                        .save_file()
+                    {
+                        let mut content = String::new();
+                        for entry in self.logs.lock().iter() {
+                            let prefix = match entry.level {
+                                LogLevel::Debug => "[DEBUG] ",
+                                LogLevel::Info => "[INFO] ",
+                                LogLevel::Error => "[ERROR] ",
+                            };
+                            content.push_str(prefix);
+                            content.push_str(&format_log_entry(entry));
+                            content.push('\n');
+                        }
+                        match std::fs::write(&path, content) {
+                            Ok(_) => push_log(
+                                &self.logs,
+                                LogLevel::Info,
+                                format!("日志已导出到 {}", path.display()),
+                            ),
+                            Err(e) => push_log(
+                                &self.logs,
+                                LogLevel::Error,
+                                format!("日志导出失败: {}", e),
+                            ),
+                        }
+                    }
+                }
+
+                ui.add_space(10.0);
+
+                egui::Frame::group(ui.style()).show(ui, |ui| {
+                    egui::ScrollArea::vertical()
+                        .max_height(400.0)
+                        .auto_shrink([false, false])
+                        .stick_to_bottom(true)
+                        .show(ui, |ui| {
+                            for entry in self.logs.lock().iter() {
+                                let visible = match entry.level {
+                                    LogLevel::Debug => self.log_filter_debug,
+                                    LogLevel::Info => self.log_filter_info,
+                                    LogLevel::Error => self.log_filter_error,
+                                };
+                                if !visible {
+                                    continue;
+                                }
+                                if !self.log_search.is_empty()
+                                    && !entry.message.contains(self.log_search.as_str())
+                                {
+                                    continue;
+                                }
+                                let (prefix, color) = match entry.level {
+                                    LogLevel::Debug => ("[DEBUG] ", egui::Color32::GRAY),
+                                    LogLevel::Info => ("[INFO] ", egui::Color32::LIGHT_GREEN),
+                                    LogLevel::Error => ("[ERROR] ", egui::Color32::LIGHT_RED),
+                                };
+                                ui.colored_label(
+                                    color,
+                                    format!("{}{}", prefix, format_log_entry(entry)),
+                                );
+                            }
+                        });
+                });
+            } else if self.selected_tab == 5 {
+                ui.label("录制一段鼠标位移/按键序列（如压枪/喷点），可在瞄准热键按下时自动回放。");
+                ui.add_space(10.0);
+
+                ui.horizontal(|ui| {
+                    ui.label("宏名称:");
+                    ui.text_edit_singleline(&mut self.macro_name_input);
+                });
+
+                ui.horizontal(|ui| {
+                    if self.macro_recorder.is_recording() {
+                        if ui.button("停止录制").clicked() {
+                            let name = if self.macro_name_input.trim().is_empty() {
+                                "未命名宏".to_string()
+                            } else {
+                                self.macro_name_input.trim().to_string()
+                            };
+                            let recorded = self.macro_recorder.stop_recording(name);
+                            push_log(
+                                &self.logs,
+                                LogLevel::Info,
+                                format!(
+                                    "宏 \"{}\" 录制完成，共 {} 个事件",
+                                    recorded.name,
+                                    recorded.events.len()
+                                ),
+                            );
+                            self.macros.lock().push(recorded);
+                            self.selected_macro_index = Some(self.macros.lock().len() - 1);
+                        }
+                    } else if ui.button("开始录制").clicked() {
+                        self.macro_recorder.start_recording();
+                        push_log(&self.logs, LogLevel::Info, "开始录制宏");
+                    }
+                });
+
+                ui.add_space(10.0);
+                ui.label("已保存的宏:");
+                egui::Frame::group(ui.style()).show(ui, |ui| {
+                    egui::ScrollArea::vertical()
+                        .max_height(150.0)
+                        .auto_shrink([false, false])
+                        .show(ui, |ui| {
+                            let macros = self.macros.lock().clone();
+                            for (index, saved) in macros.iter().enumerate() {
+                                ui.horizontal(|ui| {
+                                    ui.selectable_value(
+                                        &mut self.selected_macro_index,
+                                        Some(index),
+                                        format!("{} ({} 事件)", saved.name, saved.events.len()),
+                                    );
+                                    if ui.small_button("删除").clicked() {
+                                        self.macros.lock().remove(index);
+                                        if self.selected_macro_index == Some(index) {
+                                            self.selected_macro_index = None;
+                                        }
+                                    }
+                                });
+                            }
+                        });
+                });
+
+                ui.add_space(10.0);
+
+                ui.horizontal(|ui| {
+                    ui.label("播放速度:");
+                    ui.add(
+                        egui::Slider::new(&mut self.macro_speed, 0.1..=5.0).step_by(0.1),
+                    );
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("循环次数:");
+                    ui.add(egui::DragValue::new(&mut self.macro_loop_count).range(1..=100));
+                });
+
+                ui.checkbox(&mut self.macro_bound_to_hotkey, "瞄准热键按下时自动回放选中的宏");
+
+                ui.add_space(10.0);
+
+                if ui.button("播放选中宏").clicked() {
+                    if let Some(index) = self.selected_macro_index {
+                        if let Some(selected) = self.macros.lock().get(index).cloned() {
+                            if self.macro_playing.load(Ordering::SeqCst) {
+                                push_log(&self.logs, LogLevel::Info, "已有宏正在播放，忽略本次点击");
+                            } else {
+                                self.macro_playing.store(true, Ordering::SeqCst);
+                                let macro_playing = self.macro_playing.clone();
+                                let is_running = Arc::new(AtomicBool::new(true));
+                                let speed = self.macro_speed;
+                                let loop_count = self.macro_loop_count;
+                                thread::spawn(move || {
+                                    crate::macros::play(&selected, speed, loop_count, &is_running);
+                                    macro_playing.store(false, Ordering::SeqCst);
+                                });
+                            }
+                        }
+                    } else {
+                        push_log(&self.logs, LogLevel::Error, "请先选中一个宏");
+                    }
+                }
+
+                ui.add_space(10.0);
+
+                if ui.button("保存宏列表").clicked()
+                    && let Some(ref manager) = *self.config_manager.lock()
+                {
+                    manager.update_macros(self.macros.lock().clone());
+                    push_log(&self.logs, LogLevel::Info, "宏列表已保存");
+                }
             }
         });
         ctx.request_repaint();
     }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        if let Some(ref manager) = *self.config_manager.lock() {
+            manager.save_all(self.snapshot_config());
+        }
+    }
 }
 
+/// 日志环形缓冲区的容量上限。
+const MAX_LOG_ENTRIES: usize = 2000;
+
+/// 追加一条日志；若与最近一条日志级别和内容完全相同，则只递增其重复
+/// 计数（显示为「消息 ×N」），避免同一条高频错误刷爆缓冲区。
 fn push_log(logs: &Arc<Mutex<Vec<LogEntry>>>, level: LogLevel, message: impl Into<String>) {
+    let message = message.into();
     let mut logs = logs.lock();
+    if let Some(last) = logs.last_mut() {
+        if last.level == level && last.message == message {
+            last.count += 1;
+            return;
+        }
+    }
     logs.push(LogEntry {
         level,
-        message: message.into(),
+        message,
+        count: 1,
     });
-    if logs.len() > 500 {
-        let overflow = logs.len() - 500;
+    if logs.len() > MAX_LOG_ENTRIES {
+        let overflow = logs.len() - MAX_LOG_ENTRIES;
         logs.drain(0..overflow);
     }
 }
 
+/// 把一条日志渲染成带重复次数后缀的显示文本。
+fn format_log_entry(entry: &LogEntry) -> String {
+    if entry.count > 1 {
+        format!("{} ×{}", entry.message, entry.count)
+    } else {
+        entry.message.clone()
+    }
+}
+
 fn main() -> eframe::Result<()> {
     let default_filter = if cfg!(debug_assertions) {
         "debug"
@@ -601,7 +1475,22 @@ fn main() -> eframe::Result<()> {
     };
     let filter =
         EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(default_filter));
-    tracing_subscriber::fmt().with_env_filter(filter).init();
+
+    // 按天轮转的文件日志，与控制台输出并行；`non_blocking` 的 guard 必须存活到
+    // 进程退出才能保证缓冲区被刷盘，这里直接 leak 掉（整个进程生命周期内只创建一次）。
+    let file_appender = tracing_appender::rolling::daily("logs", "luoluo-ai.log");
+    let (file_writer, guard) = tracing_appender::non_blocking(file_appender);
+    Box::leak(Box::new(guard));
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer())
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(file_writer)
+                .with_ansi(false),
+        )
+        .init();
 
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default().with_inner_size([400.0, 300.0]),
@@ -1,36 +1,129 @@
+//! 跨平台鼠标控制：`InputBackend` 统一了「相对位移」与「查询当前光标
+//! 位置」这两个能力，Windows 上用 `SetCursorPos`/`GetCursorPos`，其余
+//! 平台通过 X11 的 XTest 扩展 (`XTestFakeRelativeMotionEvent`) 与
+//! `XQueryPointer` 实现。上层（`main.rs`/`macros.rs`）只调用模块级自由
+//! 函数 `move_relative`/`get_cursor_position`，由自由函数在编译期选定
+//! 具体后端，调用方无需区分平台。
+
 #[cfg(windows)]
 use windows::Win32::Foundation::POINT;
 #[cfg(windows)]
 use windows::Win32::UI::WindowsAndMessaging::{GetCursorPos, SetCursorPos};
 
+#[cfg(not(windows))]
+use std::ptr;
+#[cfg(not(windows))]
+use x11::{xlib, xtest};
+
+pub trait InputBackend {
+    fn move_relative(&self, dx: i32, dy: i32);
+    fn cursor_position(&self) -> Option<(i32, i32)>;
+}
+
 #[cfg(windows)]
-pub fn move_relative(dx: i32, dy: i32) {
-    unsafe {
-        let mut point = POINT::default();
-        if GetCursorPos(&mut point).is_ok() {
-            let new_x = point.x + dx;
-            let new_y = point.y + dy;
-            let _ = SetCursorPos(new_x, new_y);
+pub struct WindowsInput;
+
+#[cfg(windows)]
+impl InputBackend for WindowsInput {
+    fn move_relative(&self, dx: i32, dy: i32) {
+        unsafe {
+            let mut point = POINT::default();
+            if GetCursorPos(&mut point).is_ok() {
+                let new_x = point.x + dx;
+                let new_y = point.y + dy;
+                let _ = SetCursorPos(new_x, new_y);
+            }
+        }
+    }
+
+    fn cursor_position(&self) -> Option<(i32, i32)> {
+        unsafe {
+            let mut point = POINT::default();
+            if GetCursorPos(&mut point).is_ok() {
+                return Some((point.x, point.y));
+            }
         }
+        None
     }
 }
 
+/// 持有单个进程级共享的 `Display*`，避免每次移动/查询都重新建立一次
+/// X11 连接；裸指针本身不是 `Send`，用一个包装类型手动声明其在这里的
+/// 使用是安全的（只在持锁状态下访问）。
+#[cfg(not(windows))]
+struct DisplayHandle(*mut xlib::Display);
+
+#[cfg(not(windows))]
+unsafe impl Send for DisplayHandle {}
+
 #[cfg(not(windows))]
-pub fn move_relative(_dx: i32, _dy: i32) {}
+static X11_DISPLAY: parking_lot::Mutex<Option<DisplayHandle>> = parking_lot::Mutex::new(None);
+
+#[cfg(not(windows))]
+fn with_display<T>(f: impl FnOnce(*mut xlib::Display) -> T) -> Option<T> {
+    let mut guard = X11_DISPLAY.lock();
+    if guard.is_none() {
+        let display = unsafe { xlib::XOpenDisplay(ptr::null()) };
+        if display.is_null() {
+            return None;
+        }
+        *guard = Some(DisplayHandle(display));
+    }
+    guard.as_ref().map(|handle| f(handle.0))
+}
+
+#[cfg(not(windows))]
+pub struct X11Input;
+
+#[cfg(not(windows))]
+impl InputBackend for X11Input {
+    fn move_relative(&self, dx: i32, dy: i32) {
+        with_display(|display| unsafe {
+            xtest::XTestFakeRelativeMotionEvent(display, dx, dy, 0);
+            xlib::XFlush(display);
+        });
+    }
+
+    fn cursor_position(&self) -> Option<(i32, i32)> {
+        with_display(|display| unsafe {
+            let root = xlib::XDefaultRootWindow(display);
+            let (mut root_ret, mut child_ret) = (0, 0);
+            let (mut root_x, mut root_y, mut win_x, mut win_y) = (0, 0, 0, 0);
+            let mut mask = 0;
+            let ok = xlib::XQueryPointer(
+                display,
+                root,
+                &mut root_ret,
+                &mut child_ret,
+                &mut root_x,
+                &mut root_y,
+                &mut win_x,
+                &mut win_y,
+                &mut mask,
+            );
+            (ok != 0).then_some((root_x, root_y))
+        })
+        .flatten()
+    }
+}
+
+#[cfg(windows)]
+pub fn move_relative(dx: i32, dy: i32) {
+    WindowsInput.move_relative(dx, dy)
+}
+
+#[cfg(not(windows))]
+pub fn move_relative(dx: i32, dy: i32) {
+    X11Input.move_relative(dx, dy)
+}
 
 #[cfg(windows)]
 #[allow(dead_code)]
 pub fn get_cursor_position() -> Option<(i32, i32)> {
-    unsafe {
-        let mut point = POINT::default();
-        if GetCursorPos(&mut point).is_ok() {
-            return Some((point.x, point.y));
-        }
-    }
-    None
+    WindowsInput.cursor_position()
 }
 
 #[cfg(not(windows))]
 pub fn get_cursor_position() -> Option<(i32, i32)> {
-    None
+    X11Input.cursor_position()
 }
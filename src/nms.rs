@@ -0,0 +1,72 @@
+//! 贪心的按类别非极大值抑制（NMS），用于在把原始 YOLO 候选框交给
+//! 控制逻辑之前过滤掉互相重叠的重复检测。
+
+/// 一个待抑制的候选框：像素坐标 `[xmin, ymin, xmax, ymax]`、
+/// 综合得分（objectness * class_conf）与类别 id。
+#[derive(Clone, Copy, Debug)]
+pub struct Candidate {
+    pub bbox: [f32; 4],
+    pub score: f32,
+    pub class_id: usize,
+}
+
+fn area(b: [f32; 4]) -> f32 {
+    (b[2] - b[0]).max(0.0) * (b[3] - b[1]).max(0.0)
+}
+
+fn iou(a: [f32; 4], b: [f32; 4]) -> f32 {
+    let x1 = a[0].max(b[0]);
+    let y1 = a[1].max(b[1]);
+    let x2 = a[2].min(b[2]);
+    let y2 = a[3].min(b[3]);
+    let inter = (x2 - x1).max(0.0) * (y2 - y1).max(0.0);
+    let union = area(a) + area(b) - inter;
+    if union <= 0.0 { 0.0 } else { inter / union }
+}
+
+/// 将候选框按得分降序贪心筛选：依次取最高分框，剔除同类别中与它
+/// IoU 超过 `iou_threshold` 的其余框。零面积框直接跳过。
+pub fn greedy_nms(candidates: &[Candidate], iou_threshold: f32) -> Vec<Candidate> {
+    let mut order: Vec<usize> = (0..candidates.len())
+        .filter(|&i| area(candidates[i].bbox) > 0.0)
+        .collect();
+    order.sort_by(|&a, &b| {
+        candidates[b]
+            .score
+            .partial_cmp(&candidates[a].score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let mut suppressed = vec![false; candidates.len()];
+    let mut kept = Vec::new();
+    for &i in &order {
+        if suppressed[i] {
+            continue;
+        }
+        let a = candidates[i];
+        kept.push(a);
+        for &j in &order {
+            if j == i || suppressed[j] {
+                continue;
+            }
+            let b = candidates[j];
+            if b.class_id != a.class_id {
+                continue;
+            }
+            if iou(a.bbox, b.bbox) > iou_threshold {
+                suppressed[j] = true;
+            }
+        }
+    }
+    kept
+}
+
+/// 把候选框坐标裁剪到捕获区域 `[0, width] x [0, height]` 内。
+pub fn clamp_to_region(bbox: [f32; 4], width: f32, height: f32) -> [f32; 4] {
+    [
+        bbox[0].clamp(0.0, width),
+        bbox[1].clamp(0.0, height),
+        bbox[2].clamp(0.0, width),
+        bbox[3].clamp(0.0, height),
+    ]
+}
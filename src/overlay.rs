@@ -0,0 +1,291 @@
+//! 透明、穿透点击的置顶覆盖层窗口，用于在游戏画面上方绘制检测框，
+//! 方便用户在非窗口化模式下直接肉眼核对检测结果。
+
+use parking_lot::Mutex;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+/// 覆盖层与检测线程共享的状态：检测框（屏幕绝对像素坐标）与开关。
+#[derive(Clone)]
+pub struct OverlayState {
+    pub boxes: Arc<Mutex<Vec<[f32; 4]>>>,
+    pub enabled: Arc<AtomicBool>,
+}
+
+impl OverlayState {
+    pub fn new() -> Self {
+        Self {
+            boxes: Arc::new(Mutex::new(Vec::new())),
+            enabled: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+impl Default for OverlayState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 将屏幕像素坐标换算成 [-1, 1] 的归一化设备坐标（NDC），
+/// 与 GL 风格的覆盖层渲染管线保持一致：
+/// `x_ndc = 2 * px / screen_w - 1`，`y_ndc = 1 - 2 * py / screen_h`。
+pub fn to_ndc(px: f32, py: f32, screen_w: f32, screen_h: f32) -> (f32, f32) {
+    let x_ndc = 2.0 * px / screen_w - 1.0;
+    let y_ndc = 1.0 - 2.0 * py / screen_h;
+    (x_ndc, y_ndc)
+}
+
+/// 后台覆盖层窗口句柄；drop 前应调用 `stop` 让渲染线程退出。
+pub struct OverlayWindow {
+    running: Arc<AtomicBool>,
+    handle: Option<thread::JoinHandle<()>>,
+}
+
+impl OverlayWindow {
+    /// `screen_x`/`screen_y` 是目标显示器左上角在虚拟桌面坐标系中的偏移
+    /// （`capture::MonitorInfo::x`/`y`），覆盖层窗口据此对齐到所选显示器，
+    /// 而非总是贴在虚拟桌面原点（即主显示器）。
+    #[cfg(windows)]
+    pub fn start(
+        state: OverlayState,
+        screen_x: i32,
+        screen_y: i32,
+        screen_width: u32,
+        screen_height: u32,
+    ) -> Self {
+        let running = Arc::new(AtomicBool::new(true));
+        let thread_running = running.clone();
+        let handle = thread::spawn(move || {
+            if let Err(e) = win::run(
+                thread_running,
+                state,
+                screen_x,
+                screen_y,
+                screen_width,
+                screen_height,
+            ) {
+                tracing::error!("覆盖层窗口运行失败: {}", e);
+            }
+        });
+        Self {
+            running,
+            handle: Some(handle),
+        }
+    }
+
+    #[cfg(not(windows))]
+    pub fn start(
+        _state: OverlayState,
+        _screen_x: i32,
+        _screen_y: i32,
+        _screen_width: u32,
+        _screen_height: u32,
+    ) -> Self {
+        Self {
+            running: Arc::new(AtomicBool::new(false)),
+            handle: None,
+        }
+    }
+
+    pub fn stop(mut self) {
+        self.running.store(false, Ordering::SeqCst);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+#[cfg(windows)]
+mod win {
+    use super::OverlayState;
+    use anyhow::{Context, Result};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::Duration;
+    use windows::Win32::Foundation::{COLORREF, HWND, LPARAM, LRESULT, POINT, SIZE, WPARAM};
+    use windows::Win32::Graphics::Gdi::{
+        AlphaBlend, CreateCompatibleDC, CreateDIBSection, DeleteDC, DeleteObject,
+        SelectObject, AC_SRC_ALPHA, AC_SRC_OVER, BITMAPINFO, BITMAPINFOHEADER, BI_RGB,
+        BLENDFUNCTION, DIB_RGB_COLORS, HBITMAP, HDC,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::{
+        CreateWindowExW, DefWindowProcW, DispatchMessageW, GetMessageW, PeekMessageW,
+        PostQuitMessage, RegisterClassExW, TranslateMessage, UpdateLayeredWindow, CS_HREDRAW,
+        CS_VREDRAW, MSG, PM_REMOVE, ULW_ALPHA, WM_DESTROY, WNDCLASSEXW, WS_EX_LAYERED,
+        WS_EX_NOACTIVATE, WS_EX_TOOLWINDOW, WS_EX_TOPMOST, WS_EX_TRANSPARENT, WS_POPUP,
+    };
+    use windows::core::PCWSTR;
+
+    /// 线框颜色（BGRA，预乘 alpha）：不透明红色。
+    const STROKE: [u8; 4] = [0, 0, 255, 255];
+    const STROKE_WIDTH: i32 = 2;
+
+    pub fn run(
+        running: Arc<AtomicBool>,
+        state: OverlayState,
+        screen_x: i32,
+        screen_y: i32,
+        width: u32,
+        height: u32,
+    ) -> Result<()> {
+        unsafe {
+            let class_name: Vec<u16> = "LuoluoOverlayClass\0".encode_utf16().collect();
+            let wc = WNDCLASSEXW {
+                cbSize: std::mem::size_of::<WNDCLASSEXW>() as u32,
+                style: CS_HREDRAW | CS_VREDRAW,
+                lpfnWndProc: Some(wnd_proc),
+                lpszClassName: PCWSTR(class_name.as_ptr()),
+                ..Default::default()
+            };
+            RegisterClassExW(&wc);
+
+            let hwnd = CreateWindowExW(
+                WS_EX_LAYERED | WS_EX_TRANSPARENT | WS_EX_TOPMOST | WS_EX_TOOLWINDOW
+                    | WS_EX_NOACTIVATE,
+                PCWSTR(class_name.as_ptr()),
+                PCWSTR::null(),
+                WS_POPUP,
+                screen_x,
+                screen_y,
+                width as i32,
+                height as i32,
+                None,
+                None,
+                None,
+                None,
+            )
+            .context("创建覆盖层窗口失败")?;
+
+            let screen_dc = windows::Win32::Graphics::Gdi::GetDC(None);
+            let mem_dc = CreateCompatibleDC(screen_dc);
+
+            let mut bmi = BITMAPINFO::default();
+            bmi.bmiHeader = BITMAPINFOHEADER {
+                biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+                biWidth: width as i32,
+                biHeight: -(height as i32),
+                biPlanes: 1,
+                biBitCount: 32,
+                biCompression: BI_RGB.0,
+                ..Default::default()
+            };
+
+            let mut bits_ptr: *mut core::ffi::c_void = std::ptr::null_mut();
+            let bitmap: HBITMAP =
+                CreateDIBSection(mem_dc, &bmi, DIB_RGB_COLORS, &mut bits_ptr, None, 0)
+                    .context("创建 DIB 位图失败")?;
+            let old_bitmap = SelectObject(mem_dc, bitmap);
+
+            let pixel_count = (width * height) as usize;
+            let buffer = std::slice::from_raw_parts_mut(bits_ptr as *mut u8, pixel_count * 4);
+
+            while running.load(Ordering::SeqCst) {
+                let mut msg = MSG::default();
+                while PeekMessageW(&mut msg, None, 0, 0, PM_REMOVE).as_bool() {
+                    let _ = TranslateMessage(&msg);
+                    DispatchMessageW(&msg);
+                }
+
+                if state.enabled.load(Ordering::SeqCst) {
+                    buffer.fill(0);
+                    let boxes = state.boxes.lock().clone();
+                    for b in boxes {
+                        draw_box(buffer, width, height, b);
+                    }
+                } else {
+                    buffer.fill(0);
+                }
+
+                let size = SIZE {
+                    cx: width as i32,
+                    cy: height as i32,
+                };
+                let src_pos = POINT { x: 0, y: 0 };
+                let dst_pos = POINT { x: 0, y: 0 };
+                let blend = BLENDFUNCTION {
+                    BlendOp: AC_SRC_OVER as u8,
+                    BlendFlags: 0,
+                    SourceConstantAlpha: 255,
+                    AlphaFormat: AC_SRC_ALPHA as u8,
+                };
+                let _ = UpdateLayeredWindow(
+                    hwnd,
+                    screen_dc,
+                    Some(&dst_pos),
+                    Some(&size),
+                    mem_dc,
+                    Some(&src_pos),
+                    COLORREF(0),
+                    Some(&blend),
+                    ULW_ALPHA,
+                );
+
+                thread::sleep(Duration::from_millis(16));
+            }
+
+            SelectObject(mem_dc, old_bitmap);
+            let _ = DeleteObject(bitmap);
+            let _ = DeleteDC(mem_dc);
+            let _ = windows::Win32::Graphics::Gdi::ReleaseDC(None, screen_dc);
+            let _ = windows::Win32::UI::WindowsAndMessaging::DestroyWindow(hwnd);
+            Ok(())
+        }
+    }
+
+    use std::thread;
+
+    fn draw_box(buffer: &mut [u8], width: u32, height: u32, b: [f32; 4]) {
+        let x1 = b[0].clamp(0.0, width as f32 - 1.0) as i32;
+        let y1 = b[1].clamp(0.0, height as f32 - 1.0) as i32;
+        let x2 = b[2].clamp(0.0, width as f32 - 1.0) as i32;
+        let y2 = b[3].clamp(0.0, height as f32 - 1.0) as i32;
+        for t in 0..STROKE_WIDTH {
+            h_line(buffer, width, height, x1, x2, y1 + t);
+            h_line(buffer, width, height, x1, x2, y2 - t);
+            v_line(buffer, width, height, y1, y2, x1 + t);
+            v_line(buffer, width, height, y1, y2, x2 - t);
+        }
+    }
+
+    fn h_line(buffer: &mut [u8], width: u32, height: u32, x1: i32, x2: i32, y: i32) {
+        if y < 0 || y >= height as i32 {
+            return;
+        }
+        for x in x1.min(x2)..=x1.max(x2) {
+            put_pixel(buffer, width, height, x, y);
+        }
+    }
+
+    fn v_line(buffer: &mut [u8], width: u32, height: u32, y1: i32, y2: i32, x: i32) {
+        if x < 0 || x >= width as i32 {
+            return;
+        }
+        for y in y1.min(y2)..=y1.max(y2) {
+            put_pixel(buffer, width, height, x, y);
+        }
+    }
+
+    fn put_pixel(buffer: &mut [u8], width: u32, height: u32, x: i32, y: i32) {
+        if x < 0 || y < 0 || x >= width as i32 || y >= height as i32 {
+            return;
+        }
+        let idx = ((y as u32 * width + x as u32) * 4) as usize;
+        buffer[idx..idx + 4].copy_from_slice(&STROKE);
+    }
+
+    unsafe extern "system" fn wnd_proc(
+        hwnd: HWND,
+        msg: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+    ) -> LRESULT {
+        if msg == WM_DESTROY {
+            PostQuitMessage(0);
+            return LRESULT(0);
+        }
+        DefWindowProcW(hwnd, msg, wparam, lparam)
+    }
+}
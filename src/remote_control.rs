@@ -0,0 +1,229 @@
+//! 无头远程控制服务：换行分隔的 JSON 协议，允许第二台/无头机器实时
+//! 下发控制参数变更，并回传与本地 GUI 共用的 `LogEntry` 日志流。
+//!
+//! 协议（每行一个 JSON 对象）：
+//! - 客户端 -> 服务端：`{"command":"set_control","settings":{...ControlSettings...}}`、
+//!   `{"command":"set_conf_threshold","value":0.3}`、
+//!   `{"command":"set_capture_mode","mode":"Fullscreen"}`、
+//!   `{"command":"set_device_type","value":"cuda"}`。
+//! - 服务端 -> 客户端：新增的 `LogEntry`（与 GUI 日志框一致），有则推送。
+//!
+//! 所有命令直接写入与 GUI、检测线程共用的 `Arc<Mutex<...>>` 句柄，检测
+//! 循环每帧都会重新读取，因此改动立即生效、无需重启检测。
+//!
+//! `set_device_type` 是例外：`usls::Device` 在 `InferenceEngine::load_model`
+//! 时就已经固化进已构建的模型里，光改一个共享字符串并不会换设备。收到
+//! 这个命令时会用最近一次成功加载模型的参数（`ModelLoadParams`）在新设备
+//! 上重新调用 `load_model`，该过程本身不是无代价的（可能触发 TensorRT
+//! 重新构建引擎），但对客户端而言仍然是「发一条命令就切换」。
+
+use crate::config::{ControlSettings, RegionModeSetting};
+use crate::inference::InferenceEngine;
+use crate::{CaptureConfig, LogEntry, LogLevel, push_log};
+use anyhow::{Context, Result};
+use parking_lot::Mutex;
+use serde::Deserialize;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+#[derive(Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+enum RemoteCommand {
+    SetControl { settings: ControlSettings },
+    SetConfThreshold { value: f32 },
+    SetCaptureMode { mode: RegionModeSetting },
+    SetDeviceType { value: String },
+}
+
+/// 重新加载模型所需的参数，在每次成功 `load_model` 后由 GUI 侧更新，
+/// 供 `set_device_type` 远程命令在新设备上重新加载同一个模型。
+#[derive(Clone)]
+pub struct ModelLoadParams {
+    pub path: PathBuf,
+    pub yolo_version: u8,
+    pub input_width: u32,
+    pub input_height: u32,
+}
+
+/// 远程控制服务与 GUI/检测线程共用的句柄。
+#[derive(Clone)]
+pub struct RemoteHandles {
+    pub control_settings: Arc<Mutex<ControlSettings>>,
+    pub conf_threshold: Arc<Mutex<f32>>,
+    pub capture_config: Arc<Mutex<CaptureConfig>>,
+    pub device_type: Arc<Mutex<String>>,
+    pub logs: Arc<Mutex<Vec<LogEntry>>>,
+    pub inferencer: Arc<InferenceEngine>,
+    pub model_load_params: Arc<Mutex<Option<ModelLoadParams>>>,
+}
+
+/// 监听中的远程控制服务句柄；drop 前应调用 `stop` 结束监听与各客户端线程。
+pub struct RemoteControlServer {
+    running: Arc<AtomicBool>,
+    handle: Option<thread::JoinHandle<()>>,
+}
+
+impl RemoteControlServer {
+    pub fn start(addr: &str, handles: RemoteHandles) -> Result<Self> {
+        let listener = TcpListener::bind(addr).context("绑定远程控制端口失败")?;
+        listener
+            .set_nonblocking(true)
+            .context("设置非阻塞监听失败")?;
+        let running = Arc::new(AtomicBool::new(true));
+        let thread_running = running.clone();
+
+        let handle = thread::spawn(move || {
+            while thread_running.load(Ordering::SeqCst) {
+                match listener.accept() {
+                    Ok((stream, _addr)) => {
+                        let handles = handles.clone();
+                        let client_running = thread_running.clone();
+                        thread::spawn(move || {
+                            if let Err(e) = handle_client(stream, handles, client_running) {
+                                tracing::error!("远程控制客户端断开: {}", e);
+                            }
+                        });
+                    }
+                    Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                        thread::sleep(Duration::from_millis(50));
+                    }
+                    Err(e) => {
+                        tracing::error!("远程控制监听失败: {}", e);
+                        break;
+                    }
+                }
+            }
+        });
+
+        Ok(Self {
+            running,
+            handle: Some(handle),
+        })
+    }
+
+    pub fn stop(mut self) {
+        self.running.store(false, Ordering::SeqCst);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn handle_client(
+    stream: TcpStream,
+    handles: RemoteHandles,
+    running: Arc<AtomicBool>,
+) -> Result<()> {
+    stream.set_nodelay(true).ok();
+    let log_stream = stream.try_clone().context("克隆远程控制连接失败")?;
+    let log_running = running.clone();
+    let logs = handles.logs.clone();
+    let log_thread = thread::spawn(move || stream_logs(log_stream, logs, log_running));
+
+    let reader = BufReader::new(stream);
+    for line in reader.lines() {
+        if !running.load(Ordering::SeqCst) {
+            break;
+        }
+        let line = line.context("读取远程控制命令失败")?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<RemoteCommand>(&line) {
+            Ok(command) => apply_command(command, &handles),
+            Err(e) => tracing::error!("解析远程控制命令失败: {}", e),
+        }
+    }
+    let _ = log_thread.join();
+    Ok(())
+}
+
+fn apply_command(command: RemoteCommand, handles: &RemoteHandles) {
+    match command {
+        RemoteCommand::SetControl { settings } => {
+            *handles.control_settings.lock() = settings;
+        }
+        RemoteCommand::SetConfThreshold { value } => {
+            *handles.conf_threshold.lock() = value;
+        }
+        RemoteCommand::SetCaptureMode { mode } => {
+            handles.capture_config.lock().mode = mode.into();
+        }
+        RemoteCommand::SetDeviceType { value } => {
+            let params = handles.model_load_params.lock().clone();
+            match params {
+                Some(p) => {
+                    match handles.inferencer.load_model(
+                        p.path,
+                        &value,
+                        Some(p.yolo_version),
+                        p.input_width,
+                        p.input_height,
+                    ) {
+                        Ok(_) => {
+                            *handles.device_type.lock() = value.clone();
+                            push_log(
+                                &handles.logs,
+                                LogLevel::Info,
+                                format!("远程切换推理设备为 {} 成功", value),
+                            );
+                        }
+                        Err(e) => {
+                            push_log(
+                                &handles.logs,
+                                LogLevel::Error,
+                                format!("远程切换推理设备为 {} 失败，设备未变更: {:#}", value, e),
+                            );
+                        }
+                    }
+                }
+                None => {
+                    push_log(
+                        &handles.logs,
+                        LogLevel::Error,
+                        "尚未加载过模型，无法远程切换推理设备".to_string(),
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// 持续把日志缓冲区中新增的条目推给客户端；若缓冲区被 `push_log` 截断
+/// （超过上限被丢弃最旧的条目），则退回到重发当前整个缓冲区，不保证
+/// 历史条目不重复——这与日志框本身「固定容量滚动缓冲」的简化模型一致。
+fn stream_logs(mut stream: TcpStream, logs: Arc<Mutex<Vec<LogEntry>>>, running: Arc<AtomicBool>) {
+    let mut last_len = 0usize;
+    let mut last_tail: Option<(LogLevel, String)> = None;
+    while running.load(Ordering::SeqCst) {
+        let (pending, new_len, tail) = {
+            let logs = logs.lock();
+            let len = logs.len();
+            let tail = logs.last().map(|e| (e.level, e.message.clone()));
+            // 长度不变而末尾条目换了：缓冲区已满，丢弃最旧条目的同时追加了新条目。
+            let truncated = len < last_len || (len == last_len && tail != last_tail);
+            let start = if truncated { 0 } else { last_len };
+            (logs[start..].to_vec(), len, tail)
+        };
+        last_len = new_len;
+        last_tail = tail;
+
+        if pending.is_empty() {
+            thread::sleep(Duration::from_millis(100));
+            continue;
+        }
+        for entry in pending {
+            let Ok(json) = serde_json::to_string(&entry) else {
+                continue;
+            };
+            if writeln!(stream, "{}", json).is_err() {
+                return;
+            }
+        }
+    }
+}